@@ -14,7 +14,11 @@ criterion_group!(
   bench_complex_hann_window::bench_create_complex_hann_window,
   bench_cqt_filterbank::bench_cqt_filterbank,
   bench_cqt::bench_cqt_process,
+  bench_cqt::bench_cqt_process_silence,
+  bench_cqt::bench_magnitudes,
+  bench_cqt::bench_tiled_projection,
   bench_normalization::bench_calculate_norm,
+  bench_normalization::bench_get_calculated_norm_factor,
   bench_phase_factors::bench_get_calculated_phase_factors,
   bench_q_factor::bench_get_calculated_q_factor
 );
@@ -1,5 +1,13 @@
 use criterion::{ black_box, Criterion };
-use cqt_rs::{ create_dummy_audio_signal, CQTParams, Cqt };
+use cqt_rs::{ compute_cqt_filterbank, create_dummy_audio_signal, CQTParams, Cqt };
+use ndarray::{
+  linalg::general_mat_mul,
+  parallel::prelude::{ IndexedParallelIterator, IntoParallelIterator, ParallelIterator },
+  Array2,
+  Axis,
+  Zip,
+};
+use rustfft::num_complex::Complex;
 
 pub fn bench_cqt_process(criterion: &mut Criterion) {
   const MIN_FREQ: f32 = 14.568; // A#/Bb-1
@@ -22,4 +30,107 @@ pub fn bench_cqt_process(criterion: &mut Criterion) {
   criterion.bench_function("bench_cqt_process", |bencher| {
     bencher.iter(|| { black_box(cqt.process(&dummy_audio_signal, 1760)) })
   });
+}
+
+// Compare the sequential and parallel magnitudes to tune `PARALLEL_MAGNITUDE_THRESHOLD`,
+// the parallel version only wins on large matrices with several cores
+pub fn bench_magnitudes(criterion: &mut Criterion) {
+  const NUM_BINS: usize = 108;
+
+  for num_frames in [16, 64, 256, 1024, 4096] {
+    let cqt_output = Array2::from_shape_fn((num_frames, NUM_BINS), |(frame_idx, bin)| {
+      Complex::new(((frame_idx + bin) as f32).sin(), ((frame_idx * bin) as f32).cos())
+    });
+
+    criterion.bench_function(&format!("bench_magnitudes_sequential_{}", num_frames), |bencher| {
+      bencher.iter(|| { black_box(cqt_output.mapv(|x| x.norm())) })
+    });
+    criterion.bench_function(&format!("bench_magnitudes_parallel_{}", num_frames), |bencher| {
+      bencher.iter(|| { black_box(Zip::from(&cqt_output).par_map_collect(|x| x.norm())) })
+    });
+  }
+}
+
+// Compare the single product and the parallel tiles of the projection at 36 bins per octave,
+// mirroring `project_frames` above `PARALLEL_PROJECTION_BINS`
+pub fn bench_tiled_projection(criterion: &mut Criterion) {
+  const MIN_FREQ: f32 = 14.568; // A#/Bb-1
+  const MAX_FREQ: f32 = 7902.1; // B8
+  const BINS_PER_OCTAVE: usize = 36;
+  const SAMPLE_RATE: usize = 22000;
+  const WINDOW_LENGTH: usize = 2000;
+  const NUM_FRAMES: usize = 375;
+  const TILE_BINS: usize = 32;
+
+  let cqt_params = CQTParams::new(
+    MIN_FREQ,
+    MAX_FREQ,
+    BINS_PER_OCTAVE,
+    SAMPLE_RATE,
+    WINDOW_LENGTH
+  ).unwrap();
+
+  let filterbank = compute_cqt_filterbank(&cqt_params).unwrap();
+  let fft_frames = Array2::from_shape_fn((NUM_FRAMES, filterbank.ncols()), |(frame_idx, n)| {
+    Complex::new(((frame_idx + n) as f32).sin(), ((frame_idx * n) as f32).cos())
+  });
+  let mut cqt_output = Array2::<Complex<f32>>::zeros((NUM_FRAMES, filterbank.nrows()));
+
+  criterion.bench_function("bench_projection_single", |bencher| {
+    bencher.iter(|| {
+      general_mat_mul(
+        Complex::new(1.0, 0.0),
+        black_box(&fft_frames),
+        &filterbank.t(),
+        Complex::new(0.0, 0.0),
+        &mut cqt_output
+      )
+    })
+  });
+  criterion.bench_function("bench_projection_tiled", |bencher| {
+    bencher.iter(|| {
+      cqt_output
+        .axis_chunks_iter_mut(Axis(1), TILE_BINS)
+        .into_par_iter()
+        .zip(filterbank.axis_chunks_iter(Axis(0), TILE_BINS).into_par_iter())
+        .for_each(|(mut output_tile, filter_tile)| {
+          general_mat_mul(
+            Complex::new(1.0, 0.0),
+            black_box(&fft_frames),
+            &filter_tile.t(),
+            Complex::new(0.0, 0.0),
+            &mut output_tile
+          )
+        })
+    })
+  });
+}
+
+// Compare a silent signal, which skips the FFTs, with a signal holding a single impulse
+pub fn bench_cqt_process_silence(criterion: &mut Criterion) {
+  const MIN_FREQ: f32 = 14.568; // A#/Bb-1
+  const MAX_FREQ: f32 = 7902.1; // B8
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 22000;
+  const WINDOW_LENGTH: usize = 2000;
+
+  let cqt_params = CQTParams::new(
+    MIN_FREQ,
+    MAX_FREQ,
+    BINS_PER_OCTAVE,
+    SAMPLE_RATE,
+    WINDOW_LENGTH
+  ).unwrap();
+
+  let silence = vec![0.0; SAMPLE_RATE * 30];
+  let mut impulse = silence.clone();
+  impulse[0] = 1.0;
+  let cqt = Cqt::new(cqt_params);
+
+  criterion.bench_function("bench_cqt_process_silence", |bencher| {
+    bencher.iter(|| { black_box(cqt.process(&silence, 1760)) })
+  });
+  criterion.bench_function("bench_cqt_process_impulse", |bencher| {
+    bencher.iter(|| { black_box(cqt.process(&impulse, 1760)) })
+  });
 }
\ No newline at end of file
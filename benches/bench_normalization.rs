@@ -1,6 +1,6 @@
 use criterion::{ black_box, Criterion };
 use hann_rs::get_hann_window;
-use cqt_rs::calculate_norm;
+use cqt_rs::{ calculate_norm, get_calculated_norm_factor };
 
 pub fn bench_calculate_norm(criterion: &mut Criterion) {
   const WINDOW_LENGTH: usize = 2000;
@@ -12,4 +12,20 @@ pub fn bench_calculate_norm(criterion: &mut Criterion) {
   criterion.bench_function("calculate_norm", |bencher| {
     bencher.iter(|| black_box(calculate_norm(&hann_window)));
   });
+}
+
+pub fn bench_get_calculated_norm_factor(criterion: &mut Criterion) {
+  const WINDOW_LENGTH: usize = 2048;
+
+  let hann_window = get_hann_window(WINDOW_LENGTH).expect(
+    "Failed to get the Hann window from the lookup table"
+  );
+
+  criterion.bench_function("calculate_norm_uncached", |bencher| {
+    bencher.iter(|| black_box(calculate_norm(&hann_window)));
+  });
+
+  criterion.bench_function("get_calculated_norm_factor", |bencher| {
+    bencher.iter(|| black_box(get_calculated_norm_factor(WINDOW_LENGTH)));
+  });
 }
\ No newline at end of file
@@ -35,7 +35,7 @@ pub fn get_calculated_base_freq_ratio(bins_per_octave: usize) -> f32 {
   // Check if the sum-of-squares for the input Hann window length is in the lookup table
   if let Some(base_freq_ratio) = CALCULATED_BASE_FREQ_RATIOS.get(&bins_per_octave) {
     // If it is, return the precomputed value
-    base_freq_ratio.clone()
+    *base_freq_ratio
   } else {
     // Otherwise, for some weird reason compute the base frequency using bins per octave
     calculate_base_freq_ratio(bins_per_octave)
@@ -0,0 +1,164 @@
+/// Calculate the Kaiser window for a given window length and beta.
+///
+/// Formula used: w(n) = I0(β * sqrt(1 - (2n / (N - 1) - 1)^2)) / I0(β)
+///
+/// I0 overflows `f64` for arguments above about 700, so the ratio is computed from the
+/// exponentially scaled `e^-x * I0(x)`, which keeps the window finite for any beta.
+///
+/// # Arguments
+///
+/// * `window_length` - The window length.
+/// * `beta` - The shape parameter, trading the mainlobe width against the sidelobe level.
+///
+/// # Returns
+///
+/// The Kaiser window as a `Vec<f32>`.
+pub fn calculate_kaiser_window(window_length: usize, beta: f32) -> Vec<f32> {
+  if window_length <= 1 {
+    return vec![1.0; window_length];
+  }
+
+  let beta = beta as f64;
+  let denominator = bessel_i0_scaled(beta);
+  let last_index = (window_length - 1) as f64;
+
+  (0..window_length)
+    .map(|n| {
+      let ratio = (2.0 * (n as f64)) / last_index - 1.0;
+      let x = beta * (1.0 - ratio * ratio).max(0.0).sqrt();
+      // I0(x) / I0(β) = (e^-x * I0(x)) / (e^-β * I0(β)) * e^(x - β)
+      ((bessel_i0_scaled(x) / denominator) * (x - beta).exp()) as f32
+    })
+    .collect()
+}
+
+/// The argument above which `bessel_i0_scaled` uses the asymptotic expansion.
+const BESSEL_ASYMPTOTIC_THRESHOLD: f64 = 30.0;
+
+/// Calculate the exponentially scaled zeroth order modified Bessel function of the
+/// first kind, e^-x * I0(x), for a non-negative x.
+///
+/// Formula used for x <= 30: I0(x) = Σ ((x / 2)^k / k!)^2
+///
+/// Formula used above: e^-x * I0(x) = Σ ((2k - 1)!!)^2 / (k! * (8x)^k) / sqrt(2πx)
+///
+/// # Arguments
+///
+/// * `x` - The input value.
+///
+/// # Returns
+///
+/// The value of e^-x * I0(x), the series are summed until the terms become negligible.
+fn bessel_i0_scaled(x: f64) -> f64 {
+  if x <= BESSEL_ASYMPTOTIC_THRESHOLD {
+    let half_x = x / 2.0;
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let mut k = 1.0;
+
+    // I0(30) is about 7.8e11, so the terms can't overflow
+    loop {
+      term *= (half_x / k) * (half_x / k);
+      sum += term;
+      k += 1.0;
+
+      if term < sum * 1e-16 {
+        return sum * (-x).exp();
+      }
+    }
+  }
+
+  let mut sum = 1.0;
+  let mut term = 1.0;
+  let mut k = 1.0;
+
+  // The asymptotic terms shrink until k is about 2x, stop before they grow again
+  loop {
+    let next_term = (term * (2.0 * k - 1.0) * (2.0 * k - 1.0)) / (k * 8.0 * x);
+
+    if next_term >= term || next_term < sum * 1e-16 {
+      return sum / (2.0 * std::f64::consts::PI * x).sqrt();
+    }
+
+    term = next_term;
+    sum += term;
+    k += 1.0;
+  }
+}
+
+#[cfg(test)]
+mod test_kaiser_window {
+  use std::f64::consts::PI;
+
+  use approx::assert_abs_diff_eq;
+  use rustfft::{ FftPlanner, num_complex::Complex };
+
+  use super::*;
+
+  #[test]
+  fn test_bessel_i0_scaled() {
+    let bessel_i0 = |x: f64| bessel_i0_scaled(x) * x.exp();
+
+    assert_eq!(bessel_i0_scaled(0.0), 1.0);
+    assert_abs_diff_eq!(bessel_i0(1.0), 1.2660658777520082, epsilon = 1e-12);
+    assert_abs_diff_eq!(bessel_i0(8.0), 427.56411572180474, epsilon = 1e-9);
+    // The asymptotic expansion above the threshold
+    assert_abs_diff_eq!(bessel_i0(50.0) / 2.9325537838493398e20, 1.0, epsilon = 1e-12);
+    assert_abs_diff_eq!(bessel_i0_scaled(2000.0), 1.0 / (4000.0 * PI).sqrt(), epsilon = 1e-5);
+  }
+
+  #[test]
+  fn test_calculate_kaiser_window() {
+    let window = calculate_kaiser_window(9, 8.0);
+
+    assert_eq!(window.len(), 9);
+    assert_eq!(window[4], 1.0);
+    assert_abs_diff_eq!(window[0], window[8]);
+    let edge = (-8.0f64).exp() / bessel_i0_scaled(8.0);
+    assert_abs_diff_eq!(window[0], edge as f32, epsilon = 1e-9);
+
+    // With beta = 0 the Kaiser window is rectangular
+    assert!(calculate_kaiser_window(16, 0.0).iter().all(|&value| value == 1.0));
+  }
+
+  #[test]
+  fn test_kaiser_window_large_beta() {
+    // I0(β) overflows f64 for these betas
+    for beta in [720.0, 1500.0, 1e6] {
+      let window = calculate_kaiser_window(257, beta);
+
+      assert!(window.iter().all(|value| value.is_finite() && (0.0..=1.0).contains(value)));
+      assert_abs_diff_eq!(window[128], 1.0, epsilon = 1e-6);
+      assert_eq!(window[0], 0.0);
+    }
+  }
+
+  #[test]
+  fn test_kaiser_window_sidelobe_level() {
+    const WINDOW_LENGTH: usize = 256;
+    const OVERSAMPLING: usize = 16;
+    // Peak sidelobe level of the Kaiser window with beta = 8
+    const REFERENCE_SIDELOBE_DB: f32 = -58.5;
+
+    let window = calculate_kaiser_window(WINDOW_LENGTH, 8.0);
+
+    // Zero-pad the window to oversample its spectrum
+    let mut spectrum = vec![Complex::new(0.0, 0.0); WINDOW_LENGTH * OVERSAMPLING];
+    for (spectrum_elem, &window_elem) in spectrum.iter_mut().zip(window.iter()) {
+      spectrum_elem.re = window_elem;
+    }
+    FftPlanner::new().plan_fft_forward(spectrum.len()).process(&mut spectrum);
+
+    let magnitude: Vec<f32> = spectrum[..spectrum.len() / 2]
+      .iter()
+      .map(|x| x.norm())
+      .collect();
+
+    // Skip the mainlobe up to its first null
+    let first_null = (1..magnitude.len()).find(|&k| magnitude[k] > magnitude[k - 1]).unwrap();
+    let sidelobe = magnitude[first_null..].iter().cloned().fold(0.0, f32::max);
+    let sidelobe_db = 20.0 * (sidelobe / magnitude[0]).log10();
+
+    assert_abs_diff_eq!(sidelobe_db, REFERENCE_SIDELOBE_DB, epsilon = 1.0);
+  }
+}
\ No newline at end of file
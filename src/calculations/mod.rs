@@ -1,5 +1,7 @@
 mod base_freq_ratio;
+mod kaiser_window;
 mod phase_factors;
 
+pub use kaiser_window::calculate_kaiser_window;
 pub use phase_factors::get_calculated_phase_factors;
 pub use base_freq_ratio::get_calculated_base_freq_ratio;
\ No newline at end of file
@@ -0,0 +1,32 @@
+/// The placement of the frames relative to the input signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Centering {
+  /// The signal is padded on both sides and each frame is centered on its hop,
+  /// looking ahead of the frame start by half a window.
+  #[default]
+  Centered,
+  /// The signal is only padded at the start, frame `k` ending at sample `(k + 1) * hop_size`
+  /// so it only depends on past samples.
+  Causal,
+}
+
+impl Centering {
+  /// Compute the number of zeros padded before the signal.
+  ///
+  /// # Arguments
+  ///
+  /// * `window_length` - The length of the frames.
+  /// * `hop_size` - The number of samples between successive frames.
+  ///
+  /// # Returns
+  ///
+  /// * `usize` - The front padding, frame `k` starting at sample `k * hop_size - front_padding`.
+  pub fn front_padding(&self, window_length: usize, hop_size: usize) -> usize {
+    let signal_padding = window_length.saturating_sub(hop_size);
+
+    match self {
+      Centering::Centered => signal_padding / 2,
+      Centering::Causal => signal_padding,
+    }
+  }
+}
\ No newline at end of file
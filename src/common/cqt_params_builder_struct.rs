@@ -0,0 +1,187 @@
+use super::{ CQTParams, CQTParamsError, WindowRounding };
+
+/// `CQTParamsBuilder` builds a `CQTParams` with the options applied at construction.
+///
+/// The options that can be changed afterwards are set with the `with_*` methods of `CQTParams`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CQTParamsBuilder {
+  min_freq: f32,
+  max_freq: f32,
+  bins_per_octave: usize,
+  sample_rate: usize,
+  window_length: usize,
+  strict_window_length: bool,
+  window_rounding: WindowRounding,
+  max_bins: Option<usize>,
+}
+
+impl CQTParamsBuilder {
+  /// Create a new CQTParamsBuilder with the provided parameters.
+  ///
+  /// # Arguments
+  ///
+  /// * `min_freq` - The minimum frequency in Hz.
+  /// * `max_freq` - The maximum frequency in Hz.
+  /// * `bins_per_octave` - The number of frequency bins per octave.
+  /// * `sample_rate` - The audio sample rate in Hz.
+  /// * `window_length` - The length of the analysis window.
+  pub fn new(
+    min_freq: f32,
+    max_freq: f32,
+    bins_per_octave: usize,
+    sample_rate: usize,
+    window_length: usize
+  ) -> Self {
+    Self {
+      min_freq,
+      max_freq,
+      bins_per_octave,
+      sample_rate,
+      window_length,
+      strict_window_length: false,
+      window_rounding: WindowRounding::Up,
+      max_bins: None,
+    }
+  }
+
+  /// Enable or disable the strict window length.
+  ///
+  /// By default the window length is rounded up to the next power of two. When enabled,
+  /// `build` rejects a window length that isn't already a power of two instead.
+  pub fn strict_window_length(mut self, strict_window_length: bool) -> Self {
+    self.strict_window_length = strict_window_length;
+    self
+  }
+
+  /// Set how the window length is adjusted.
+  ///
+  /// By default the window length is rounded up to the next power of two. `Nearest`
+  /// rounds it to the closest power of two, which may be shorter, and `Exact` keeps it.
+  pub fn window_rounding(mut self, window_rounding: WindowRounding) -> Self {
+    self.window_rounding = window_rounding;
+    self
+  }
+
+  /// Set the maximum number of bins.
+  ///
+  /// By default the number of bins is unbounded. When set, `build` rejects a frequency
+  /// range and bins per octave resulting in more bins, before any filterbank is allocated.
+  pub fn max_bins(mut self, max_bins: usize) -> Self {
+    self.max_bins = Some(max_bins);
+    self
+  }
+
+  /// Build the CQTParams.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if any of the parameters are invalid, see `CQTParams::new`, or if the
+  /// strict window length is enabled and the window length isn't a power of two, or
+  /// `TooManyBins` if the number of bins exceeds the maximum number of bins.
+  pub fn build(self) -> Result<CQTParams, CQTParamsError> {
+    // A zero window length is rejected by `CQTParams::new`
+    if
+      self.strict_window_length &&
+      self.window_length > 0 &&
+      !self.window_length.is_power_of_two()
+    {
+      return Err(CQTParamsError::WindowLengthNotPowerOfTwo);
+    }
+
+    let cqt_params = CQTParams::new_with_rounding(
+      self.min_freq,
+      self.max_freq,
+      self.bins_per_octave,
+      self.sample_rate,
+      self.window_length,
+      self.window_rounding
+    )?;
+
+    match self.max_bins {
+      Some(max_bins) => cqt_params.with_max_bins_cap(max_bins),
+      None => Ok(cqt_params),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_cqt_params_builder() {
+    let cqt_params = CQTParamsBuilder::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).build();
+    let expected = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    );
+
+    assert_eq!(cqt_params, expected);
+  }
+
+  #[test]
+  fn test_cqt_params_builder_strict_window_length() {
+    let cqt_params = CQTParamsBuilder::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 4000)
+      .strict_window_length(true)
+      .build();
+    assert_eq!(cqt_params, Err(CQTParamsError::WindowLengthNotPowerOfTwo));
+
+    let cqt_params = CQTParamsBuilder::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 4096)
+      .strict_window_length(true)
+      .build();
+    assert_eq!(cqt_params.unwrap().window_length, 4096);
+
+    // The window length is rounded up by default
+    let cqt_params = CQTParamsBuilder::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 4000)
+      .build();
+    assert_eq!(cqt_params.unwrap().window_length, 4096);
+  }
+
+  #[test]
+  fn test_cqt_params_builder_window_rounding() {
+    let build = |window_rounding: WindowRounding| {
+      CQTParamsBuilder::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 3000)
+        .window_rounding(window_rounding)
+        .build()
+        .unwrap()
+    };
+
+    // 2048 is closer to 3000 than 4096
+    assert_eq!(build(WindowRounding::Up).window_length, 4096);
+    assert_eq!(build(WindowRounding::Nearest).window_length, 2048);
+    assert_eq!(build(WindowRounding::Exact).window_length, 3000);
+
+    let cqt_params = build(WindowRounding::Exact);
+    assert_eq!(cqt_params.requested_window_length(), 3000);
+    assert_eq!(cqt_params.hann_window().len(), 3000);
+    assert_eq!(cqt_params.phase_factors().len(), 3000);
+  }
+
+  #[test]
+  fn test_cqt_params_builder_max_bins() {
+    // 48 bins per octave over the 9 octaves from 20 Hz
+    let build = |max_bins: usize| {
+      CQTParamsBuilder::new(MIN_FREQ, MAX_FREQ, 48, SAMPLE_RATE, WINDOW_LENGTH)
+        .max_bins(max_bins)
+        .build()
+    };
+
+    assert_eq!(build(500).unwrap().num_bins(), 432);
+    assert_eq!(build(400), Err(CQTParamsError::TooManyBins { requested: 432, cap: 400 }));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use super::{ Centering, CQTParams, FilterNorm, PhaseConvention, WindowType };
+
+/// A hashable key of every setting of `CQTParams` that changes the filterbank or the
+/// output of the transform, see `CQTParams::config_key`.
+///
+/// The floating point settings are bit-cast to `u32`. The built-in analysis windows are
+/// keyed by their `WindowType`, while the samples of a custom analysis window or of a
+/// synthesis window are held in the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CQTParamsKey {
+  min_freq: u32,
+  max_freq: u32,
+  bins_per_octave: usize,
+  sample_rate: usize,
+  window_length: usize,
+  num_bins: usize,
+  window_type: WindowTypeKey,
+  custom_window: Option<Arc<[u32]>>,
+  synth_window: Option<Arc<[u32]>>,
+  edge_taper: bool,
+  filter_norm: FilterNorm,
+  filterbank_threshold: u32,
+  remove_dc: bool,
+  pre_emphasis: Option<u32>,
+  octave_windows: bool,
+  single_sided: bool,
+  clamp_max_freq: bool,
+  tuning_cents: u32,
+  centering: Centering,
+  phase_convention: PhaseConvention,
+}
+
+impl CQTParamsKey {
+  /// Create the key of the given parameters.
+  pub(crate) fn new(cqt_params: &CQTParams) -> Self {
+    Self {
+      min_freq: cqt_params.min_freq.to_bits(),
+      max_freq: cqt_params.max_freq.to_bits(),
+      bins_per_octave: cqt_params.bins_per_octave,
+      sample_rate: cqt_params.sample_rate,
+      window_length: cqt_params.window_length,
+      num_bins: cqt_params.num_bins(),
+      window_type: WindowTypeKey::new(cqt_params.window_type()),
+      custom_window: cqt_params
+        .custom_window()
+        .then(|| window_samples(&cqt_params.hann_window)),
+      synth_window: cqt_params.custom_synth_window().map(window_samples),
+      edge_taper: cqt_params.edge_taper(),
+      filter_norm: cqt_params.filter_norm(),
+      filterbank_threshold: cqt_params.filterbank_threshold().to_bits(),
+      remove_dc: cqt_params.remove_dc(),
+      pre_emphasis: cqt_params.pre_emphasis().map(f32::to_bits),
+      octave_windows: cqt_params.octave_windows(),
+      single_sided: cqt_params.single_sided(),
+      clamp_max_freq: cqt_params.clamped_max_freq(),
+      tuning_cents: cqt_params.tuning_cents().to_bits(),
+      centering: cqt_params.centering(),
+      phase_convention: cqt_params.phase_convention(),
+    }
+  }
+}
+
+/// The `WindowType` with its Kaiser beta bit-cast to `u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum WindowTypeKey {
+  Hann,
+  Kaiser {
+    beta: u32,
+  },
+}
+
+impl WindowTypeKey {
+  fn new(window_type: WindowType) -> Self {
+    match window_type {
+      WindowType::Hann => WindowTypeKey::Hann,
+      WindowType::Kaiser { beta } => WindowTypeKey::Kaiser { beta: beta.to_bits() },
+    }
+  }
+}
+
+/// Bit-cast the samples of a window to `u32`, so they can be compared and hashed exactly.
+fn window_samples(window: &[f32]) -> Arc<[u32]> {
+  window.iter().map(|value| value.to_bits()).collect()
+}
@@ -4,10 +4,24 @@ use hann_rs::get_hann_window;
 use ndarray::Array1;
 
 use crate::{
-  complex_hann_window::{ calculate_norm, get_calculated_q_factor },
+  complex_hann_window::{
+    calculate_norm,
+    get_calculated_norm_factor_of,
+    get_calculated_q_factor,
+  },
   calculations::{ get_calculated_base_freq_ratio, get_calculated_phase_factors },
 };
 
+use super::{
+  Centering,
+  CQTParamsBuilder,
+  CQTParamsKey,
+  FilterNorm,
+  PhaseConvention,
+  WindowRounding,
+  WindowType,
+};
+
 /// Error type for the CQTParams.
 #[derive(Debug, PartialEq)]
 pub enum CQTParamsError {
@@ -16,6 +30,16 @@ pub enum CQTParamsError {
   InvalidBinsPerOctave,
   InvalidSampleRate,
   InvalidWindowLength,
+  WindowLengthTooLarge,
+  WindowLengthNotPowerOfTwo,
+  InvalidKaiserBeta,
+  InvalidNumBins,
+  MaxFrequencyAboveNyquist,
+  InvalidWindow,
+  TooManyBins {
+    requested: usize,
+    cap: usize,
+  },
 }
 
 // Implement the Error trait for the CQTParamsError
@@ -27,12 +51,12 @@ impl fmt::Display for CQTParamsError {
     // Write the error message to the Formatter
     match self {
       CQTParamsError::InvalidMinFrequency => {
-        write!(f, "Invalid minimum frequency: must be a positive number")
+        write!(f, "Invalid minimum frequency: must be a finite positive number")
       }
       CQTParamsError::InvalidMaxFrequency => {
         write!(
           f,
-          "Invalid maximum frequency: must be a positive number and greater than the minimum frequency"
+          "Invalid maximum frequency: must be a finite number greater than the minimum frequency"
         )
       }
       CQTParamsError::InvalidBinsPerOctave => {
@@ -44,6 +68,27 @@ impl fmt::Display for CQTParamsError {
       CQTParamsError::InvalidWindowLength => {
         write!(f, "Invalid window length: must be a positive integer")
       }
+      CQTParamsError::WindowLengthTooLarge => {
+        write!(f, "Window length too large: rounding up to a power of two overflows")
+      }
+      CQTParamsError::WindowLengthNotPowerOfTwo => {
+        write!(f, "Invalid window length: must be a power of two with the strict window length")
+      }
+      CQTParamsError::InvalidKaiserBeta => {
+        write!(f, "Invalid Kaiser beta: must be a finite non-negative number")
+      }
+      CQTParamsError::InvalidNumBins => {
+        write!(f, "Invalid number of bins: must be a positive integer")
+      }
+      CQTParamsError::MaxFrequencyAboveNyquist => {
+        write!(f, "Invalid number of bins: the highest center frequency must be below Nyquist")
+      }
+      CQTParamsError::InvalidWindow => {
+        write!(f, "Invalid window: must only hold finite values")
+      }
+      CQTParamsError::TooManyBins { requested, cap } => {
+        write!(f, "Too many bins: {} bins requested, the cap is {}", requested, cap)
+      }
     }
   }
 }
@@ -58,11 +103,26 @@ pub struct CQTParams {
   pub sample_rate: usize,
   pub window_length: usize,
   pub hann_window: Vec<f32>,
+  custom_window: bool,
+  requested_window_length: usize,
   num_bins: usize,
   q_factor: f32,
   base_freq_ratio: f32,
   norm_factor: f32,
   phase_factors: Array1<f32>,
+  edge_taper: bool,
+  filter_norm: FilterNorm,
+  filterbank_threshold: f32,
+  remove_dc: bool,
+  pre_emphasis: Option<f32>,
+  octave_windows: bool,
+  single_sided: bool,
+  clamp_max_freq: bool,
+  tuning_cents: f32,
+  window_type: WindowType,
+  centering: Centering,
+  phase_convention: PhaseConvention,
+  synth_window: Option<Vec<f32>>,
 }
 
 impl CQTParams {
@@ -74,11 +134,12 @@ impl CQTParams {
   /// * `max_freq` - The maximum frequency in Hz.
   /// * `bins_per_octave` - The number of frequency bins per octave.
   /// * `sample_rate` - The audio sample rate in Hz.
-  /// * `window_length` - The length of the analysis window.
+  /// * `window_length` - The length of the analysis window, rounded up to the next power of two.
   ///
   /// # Errors
   ///
-  /// Returns an error if any of the input parameters are not positive integers.
+  /// Returns an error if any of the input parameters are not positive integers, or if the
+  /// frequencies aren't finite.
   pub fn new(
     min_freq: f32,
     max_freq: f32,
@@ -86,11 +147,30 @@ impl CQTParams {
     sample_rate: usize,
     window_length: usize
   ) -> Result<Self, CQTParamsError> {
-    if min_freq <= 0.0 {
+    Self::new_with_rounding(
+      min_freq,
+      max_freq,
+      bins_per_octave,
+      sample_rate,
+      window_length,
+      WindowRounding::Up
+    )
+  }
+
+  /// Create a new CQTParams instance, adjusting the window length with `window_rounding`.
+  pub(crate) fn new_with_rounding(
+    min_freq: f32,
+    max_freq: f32,
+    bins_per_octave: usize,
+    sample_rate: usize,
+    window_length: usize,
+    window_rounding: WindowRounding
+  ) -> Result<Self, CQTParamsError> {
+    if !min_freq.is_finite() || min_freq <= 0.0 {
       return Err(CQTParamsError::InvalidMinFrequency);
     }
 
-    if max_freq <= min_freq {
+    if !max_freq.is_finite() || max_freq <= min_freq {
       return Err(CQTParamsError::InvalidMaxFrequency);
     }
 
@@ -105,11 +185,14 @@ impl CQTParams {
     if window_length == 0 {
       return Err(CQTParamsError::InvalidWindowLength);
     }
-    // Computes the smallest power of two greater than or equal to window_length
+    let requested_window_length = window_length;
+    // Computes the smallest power of two greater than or equal to window_length by default
     // When the input length is not a power of two, the algorithm's performance may degrade.
-    let window_length = window_length.next_power_of_two();
+    let window_length = window_rounding
+      .round(window_length)
+      .ok_or(CQTParamsError::WindowLengthTooLarge)?;
     // Compute the number of bins K = B * log2(f_max / f_min):
-    let num_bins = ((bins_per_octave as f32) * (max_freq / min_freq).log2().ceil()) as usize;
+    let num_bins = calculate_num_bins(min_freq, max_freq, bins_per_octave, false);
     // Compute the base frequency ratio
     let base_freq_ratio = get_calculated_base_freq_ratio(bins_per_octave);
     // Compute the Q factor
@@ -117,7 +200,7 @@ impl CQTParams {
     // Compute the Hann window
     let hann_window = get_hann_window(window_length).unwrap();
     // Compute the normalization factor
-    let norm_factor = calculate_norm(&hann_window).unwrap();
+    let norm_factor = get_calculated_norm_factor_of(&hann_window).unwrap();
     // Compute phase factors
     let phase_factors = get_calculated_phase_factors(window_length, sample_rate);
 
@@ -127,15 +210,223 @@ impl CQTParams {
       bins_per_octave,
       sample_rate,
       window_length,
+      requested_window_length,
       num_bins,
       q_factor,
       base_freq_ratio,
       hann_window,
+      custom_window: false,
       norm_factor,
       phase_factors,
+      edge_taper: false,
+      filter_norm: FilterNorm::Global,
+      filterbank_threshold: 0.0,
+      remove_dc: false,
+      pre_emphasis: None,
+      octave_windows: false,
+      single_sided: false,
+      clamp_max_freq: false,
+      tuning_cents: 0.0,
+      window_type: WindowType::Hann,
+      centering: Centering::Centered,
+      phase_convention: PhaseConvention::Negative,
+      synth_window: None,
     })
   }
 
+  /// Create a new CQTParamsBuilder with the provided parameters, see `CQTParamsBuilder`.
+  pub fn builder(
+    min_freq: f32,
+    max_freq: f32,
+    bins_per_octave: usize,
+    sample_rate: usize,
+    window_length: usize
+  ) -> CQTParamsBuilder {
+    CQTParamsBuilder::new(min_freq, max_freq, bins_per_octave, sample_rate, window_length)
+  }
+
+  /// Create a new CQTParams instance with a window supplied by the caller.
+  ///
+  /// The window is used as is instead of the Hann window: the window length is its
+  /// length, without rounding up to a power of two, and the normalization factor is
+  /// computed from its samples. The window can't be used with the per-octave windows, whose
+  /// lengths differ from its own, see `Cqt::with_planner`.
+  ///
+  /// # Arguments
+  ///
+  /// * `min_freq` - The minimum frequency in Hz.
+  /// * `max_freq` - The maximum frequency in Hz.
+  /// * `bins_per_octave` - The number of frequency bins per octave.
+  /// * `sample_rate` - The audio sample rate in Hz.
+  /// * `window` - The analysis window.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if any of the input parameters are invalid as in `new`, if the
+  /// window is empty or if it holds values that aren't finite.
+  pub fn with_window(
+    min_freq: f32,
+    max_freq: f32,
+    bins_per_octave: usize,
+    sample_rate: usize,
+    window: Vec<f32>
+  ) -> Result<Self, CQTParamsError> {
+    if window.is_empty() {
+      return Err(CQTParamsError::InvalidWindowLength);
+    }
+
+    if !window.iter().all(|value| value.is_finite()) {
+      return Err(CQTParamsError::InvalidWindow);
+    }
+
+    let window_length = window.len();
+    let mut cqt_params = Self::new(
+      min_freq,
+      max_freq,
+      bins_per_octave,
+      sample_rate,
+      window_length
+    )?;
+
+    cqt_params.window_length = window_length;
+    cqt_params.norm_factor = calculate_norm(&window).map_err(|_| CQTParamsError::InvalidWindow)?;
+    cqt_params.phase_factors = get_calculated_phase_factors(window_length, sample_rate);
+    cqt_params.hann_window = window;
+    cqt_params.custom_window = true;
+
+    Ok(cqt_params)
+  }
+
+  /// Create a new CQTParams instance from the number of bins instead of the maximum frequency.
+  ///
+  /// The maximum frequency is the center frequency of the last bin, and the number of bins
+  /// is clamped to it.
+  ///
+  /// # Arguments
+  ///
+  /// * `min_freq` - The minimum frequency in Hz, the center frequency of the first bin.
+  /// * `bins_per_octave` - The number of frequency bins per octave.
+  /// * `n_bins` - The total number of bins.
+  /// * `sample_rate` - The audio sample rate in Hz.
+  /// * `window_length` - The length of the analysis window, rounded up to the next power of two.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if any of the input parameters are not positive integers, or if the
+  /// center frequency of the last bin isn't below the Nyquist frequency.
+  pub fn with_n_bins(
+    min_freq: f32,
+    bins_per_octave: usize,
+    n_bins: usize,
+    sample_rate: usize,
+    window_length: usize
+  ) -> Result<Self, CQTParamsError> {
+    if bins_per_octave == 0 {
+      return Err(CQTParamsError::InvalidBinsPerOctave);
+    }
+
+    if n_bins == 0 {
+      return Err(CQTParamsError::InvalidNumBins);
+    }
+
+    // Validate the parameters with the upper edge of the last bin as the maximum frequency
+    let upper_freq = min_freq * (2.0f32).powf((n_bins as f32) / (bins_per_octave as f32));
+    let mut cqt_params = Self::new(
+      min_freq,
+      upper_freq,
+      bins_per_octave,
+      sample_rate,
+      window_length
+    )?;
+
+    // Center frequency of the last bin
+    let max_freq = cqt_params.center_freq(n_bins - 1);
+
+    if max_freq >= cqt_params.nyquist() {
+      return Err(CQTParamsError::MaxFrequencyAboveNyquist);
+    }
+
+    cqt_params.max_freq = max_freq;
+    cqt_params.num_bins = n_bins;
+    cqt_params.clamp_max_freq = true;
+
+    Ok(cqt_params)
+  }
+
+  /// Enable or disable the cosine edge taper of the complex Hann windows.
+  ///
+  /// When enabled, filters whose theoretical support exceeds `window_length`
+  /// get their edges tapered with a cosine ramp instead of being hard truncated,
+  /// which reduces the spectral leakage of the lowest bins. A custom window set
+  /// with `with_window` is used as is.
+  pub fn with_edge_taper(mut self, edge_taper: bool) -> Self {
+    self.edge_taper = edge_taper;
+    self
+  }
+
+  /// Enable or disable the per-bin energy normalization of the complex Hann windows.
+  ///
+  /// By default every window is scaled by the same `norm_factor`. When enabled, each
+  /// window is instead scaled by its own factor so it has unit energy, compensating the
+  /// differing supports of the bins. Shorthand for `with_filter_norm(FilterNorm::PerBin)`.
+  pub fn with_per_bin_norm(mut self, per_bin_norm: bool) -> Self {
+    self.filter_norm = if per_bin_norm { FilterNorm::PerBin } else { FilterNorm::Global };
+    self
+  }
+
+  /// Set the normalization of the complex windows of the filterbank.
+  ///
+  /// With `FilterNorm::UnityPeak`, each window is scaled so a unit-amplitude sine at its
+  /// center frequency, windowed like the frames of `Cqt::process`, reads a magnitude of 1.
+  /// The single-sided scaling doubles it and should stay disabled to read 1.
+  pub fn with_filter_norm(mut self, filter_norm: FilterNorm) -> Self {
+    self.filter_norm = filter_norm;
+    self
+  }
+
+  /// Set the threshold below which the filterbank coefficients are zeroed.
+  ///
+  /// The threshold is relative to the largest magnitude of each filter: the coefficients
+  /// whose magnitude is below `threshold` times that magnitude are set to exactly zero,
+  /// which removes the floating point noise of the negligible coefficients. The default
+  /// threshold of 0.0 keeps every coefficient.
+  pub fn with_filterbank_threshold(mut self, threshold: f32) -> Self {
+    self.filterbank_threshold = threshold;
+    self
+  }
+
+  /// Enable or disable the removal of the DC offset of each frame.
+  ///
+  /// When enabled, the mean of each frame is subtracted before windowing, so a DC offset
+  /// doesn't leak into the lowest bins.
+  pub fn with_remove_dc(mut self, remove_dc: bool) -> Self {
+    self.remove_dc = remove_dc;
+    self
+  }
+
+  /// Set the pre-emphasis coefficient applied to the signal before framing.
+  ///
+  /// With `Some(alpha)`, the signal is filtered with `y[n] = x[n] - alpha * x[n - 1]`,
+  /// `x[-1]` being 0, which boosts the high frequencies, e.g. 0.97 for speech. The default
+  /// `None` leaves the signal unchanged. The filter applies to the whole signals given to
+  /// `Cqt::process` and its variants, not to frames given one at a time.
+  pub fn with_pre_emphasis(mut self, pre_emphasis: Option<f32>) -> Self {
+    self.pre_emphasis = pre_emphasis;
+    self
+  }
+
+  /// Return the window length as requested in `new`, before rounding.
+  pub fn requested_window_length(&self) -> usize {
+    self.requested_window_length
+  }
+
+  /// Return the effective window length, the requested window length adjusted by the
+  /// `WindowRounding`, or the length of the custom window. All the window sized arrays
+  /// use this length.
+  pub fn effective_window_length(&self) -> usize {
+    self.window_length
+  }
+
   /// Return the number of bins in the filter bank.
   pub fn num_bins(&self) -> usize {
     self.num_bins
@@ -151,145 +442,1345 @@ impl CQTParams {
     self.norm_factor
   }
 
-  /// Calculate the center frequency for a given bin. f_c = f_min * r^n
+  /// Calculate the center frequency for a given bin. f_c = f_min * 2^(cents / 1200) * r^n
   pub fn center_freq(&self, bin: usize) -> f32 {
-    self.min_freq * self.base_freq_ratio.powf(bin as f32)
+    self.min_freq * self.tuning_ratio() * self.base_freq_ratio.powf(bin as f32)
   }
 
-  /// Return a reference to the phase factors array.
-  pub fn phase_factors(&self) -> &Array1<f32> {
-    &self.phase_factors
+  /// Calculate the center frequency of every bin of the filter bank.
+  pub fn center_freqs(&self) -> Array1<f32> {
+    Array1::from_shape_fn(self.num_bins, |bin| self.center_freq(bin))
   }
 
-  /// Return a reference to the Hann window array.
-  pub fn hann_window(&self) -> &Vec<f32> {
-    &self.hann_window
+  /// Calculate the frequency of every FFT bin of a window, from 0 Hz up to the Nyquist
+  /// frequency. f_k = k * sample_rate / window_length, for k in 0..window_length / 2 + 1
+  pub fn fft_bin_freqs(&self) -> Array1<f32> {
+    let bin_width = (self.sample_rate as f32) / (self.window_length as f32);
+    Array1::from_shape_fn(self.window_length / 2 + 1, |k| (k as f32) * bin_width)
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use std::f32::consts::PI;
+  /// Return the Nyquist frequency, half the sample rate, in Hz.
+  pub fn nyquist(&self) -> f32 {
+    (self.sample_rate as f32) / 2.0
+  }
 
-  use hann_rs::get_hann_window;
+  /// Return the lowest and highest center frequencies of the filter bank in Hz.
+  pub fn coverage(&self) -> (f32, f32) {
+    (self.center_freq(0), self.center_freq(self.num_bins.saturating_sub(1)))
+  }
+
+  /// Create a copy of the parameters adapted to a new sample rate.
+  ///
+  /// The musical parameters (frequency range, bins per octave and number of bins), the
+  /// window and the options are kept, only the phase factors are recomputed for the new rate.
+  ///
+  /// # Arguments
+  ///
+  /// * `new_rate` - The new audio sample rate in Hz.
+  ///
+  /// # Errors
+  ///
+  /// Returns `InvalidSampleRate` if the new rate is 0, or `MaxFrequencyAboveNyquist` if the
+  /// highest center frequency isn't below the new Nyquist frequency.
+  pub fn rescale_sample_rate(&self, new_rate: usize) -> Result<CQTParams, CQTParamsError> {
+    if new_rate == 0 {
+      return Err(CQTParamsError::InvalidSampleRate);
+    }
 
-  use crate::complex_hann_window::{ get_calculated_q_factor, calculate_norm };
+    if self.coverage().1 >= (new_rate as f32) / 2.0 {
+      return Err(CQTParamsError::MaxFrequencyAboveNyquist);
+    }
 
-  use super::*;
+    // Keep the sign of the phase convention
+    let sign = self.phase_convention.sign() * PhaseConvention::Negative.sign();
+    let phase_factors = get_calculated_phase_factors(self.window_length, new_rate);
 
-  const MIN_FREQ: f32 = 20.0;
-  const MAX_FREQ: f32 = 7902.1;
-  const BINS_PER_OCTAVE: usize = 12;
-  const SAMPLE_RATE: usize = 44100;
-  const WINDOW_LENGTH: usize = 4096;
+    Ok(CQTParams {
+      sample_rate: new_rate,
+      hann_window: self.hann_window.clone(),
+      synth_window: self.synth_window.clone(),
+      phase_factors: phase_factors.mapv(|phase| sign * phase),
+      ..*self
+    })
+  }
 
-  #[test]
-  fn test_cqt_params_ok() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    );
+  /// Return a hashable key of the configuration, e.g. to cache `Cqt` instances.
+  ///
+  /// The key holds the construction inputs and every option set through the `with_*`
+  /// methods, so parameters giving a different filterbank or output get different keys.
+  /// Unlike `PartialEq`, the derived window and phase arrays are left out: the built-in
+  /// windows are keyed by their `WindowType`, and only the samples of a custom analysis
+  /// window or of a synthesis window are copied into the key.
+  pub fn config_key(&self) -> CQTParamsKey {
+    CQTParamsKey::new(self)
+  }
 
-    assert!(cqt_params.is_ok());
+  /// Set the tuning deviation in cents applied to every center frequency.
+  ///
+  /// When the number of bins is clamped to `max_freq`, it is recomputed from the tuned
+  /// center frequencies, so the highest one stays less than or equal to `max_freq`.
+  pub fn with_tuning_cents(mut self, tuning_cents: f32) -> Self {
+    self.set_tuning_cents(tuning_cents);
+    self
   }
 
-  #[test]
-  fn test_cqt_params_q_factor() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    ).unwrap();
-    let expected_q_factor = get_calculated_q_factor(BINS_PER_OCTAVE).unwrap();
+  /// Set the tuning deviation in cents in place, recomputing the clamped number of bins.
+  pub(crate) fn set_tuning_cents(&mut self, tuning_cents: f32) {
+    self.tuning_cents = tuning_cents;
 
-    assert_eq!(cqt_params.q_factor(), expected_q_factor);
+    if self.clamp_max_freq {
+      self.num_bins = self.calculated_num_bins();
+    }
   }
 
-  #[test]
-  fn test_cqt_params_num_bins() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    ).unwrap();
-    let expected_num_bins = ((BINS_PER_OCTAVE as f32) *
-      (MAX_FREQ / MIN_FREQ).log2().ceil()) as usize;
+  /// Return the tuning deviation in cents.
+  pub fn tuning_cents(&self) -> f32 {
+    self.tuning_cents
+  }
 
-    assert_eq!(cqt_params.num_bins(), expected_num_bins);
+  /// Return the frequency ratio of the tuning deviation. 2^(cents / 1200)
+  fn tuning_ratio(&self) -> f32 {
+    (2f32).powf(self.tuning_cents / 1200.0)
   }
 
-  #[test]
-  fn test_cqt_params_center_freq() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    ).unwrap();
+  /// Enable or disable the per-octave adaptive window lengths.
+  ///
+  /// A single global window is a compromise: too short for the bass and needlessly long
+  /// for the treble. When enabled, each octave is analyzed with its own window, the smallest
+  /// power of two holding the theoretical support (Q * sample_rate / f) of its lowest bin,
+  /// capped at `window_length`. The bass octaves keep the full window while the treble octaves
+  /// get much shorter ones, so a long `window_length` can be chosen for the low-frequency
+  /// resolution without smearing the high frequencies in time.
+  pub fn with_octave_windows(mut self, octave_windows: bool) -> Self {
+    self.octave_windows = octave_windows;
+    self
+  }
 
-    let expected_center_freq = MIN_FREQ * (2f32).powf(1.0 / (BINS_PER_OCTAVE as f32)).powf(40.0);
+  /// Enable or disable the single-sided magnitude scaling.
+  ///
+  /// A real signal splits its energy between the positive and negative frequencies,
+  /// and each CQT bin only captures the positive half. When enabled, the magnitudes
+  /// of the bins strictly between DC and Nyquist are doubled to represent the full
+  /// signal energy, matching single-sided STFT references.
+  pub fn with_single_sided(mut self, single_sided: bool) -> Self {
+    self.single_sided = single_sided;
+    self
+  }
 
-    assert_eq!(cqt_params.center_freq(0), MIN_FREQ);
-    assert_eq!(cqt_params.center_freq(40), expected_center_freq);
+  /// Calculate the bandwidth in Hz of a given bin. ∆f = f_c / Q
+  pub fn bandwidth_hz(&self, bin: usize) -> f32 {
+    self.center_freq(bin) / self.q_factor
   }
 
-  #[test]
-  fn test_cqt_params_phase_factors() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    ).unwrap();
+  /// Calculate the overlap factor of a given bin with the next one, the ratio of its
+  /// bandwidth to the spacing of their center frequencies.
+  ///
+  /// A value near 1 means the filters tile the spectrum, a value well above 1 means
+  /// they overlap heavily and a value below 1 leaves gaps between them. The spacing of
+  /// the last bin is taken to the center frequency a bin above it would have.
+  pub fn bin_overlap(&self, bin: usize) -> f32 {
+    self.bandwidth_hz(bin) / (self.center_freq(bin + 1) - self.center_freq(bin))
+  }
 
-    assert_eq!(cqt_params.phase_factors().len(), WINDOW_LENGTH);
-    assert_eq!(cqt_params.phase_factors()[0], 0.0);
-    assert_eq!(
-      cqt_params.phase_factors()[WINDOW_LENGTH - 1],
-      (-2.0 * PI * ((WINDOW_LENGTH - 1) as f32)) / (SAMPLE_RATE as f32)
-    );
+  /// Calculate the largest hop size sampling the output of a given bin without aliasing.
+  ///
+  /// The complex output of a bin is band-limited to its bandwidth ∆f, so by the sampling
+  /// theorem its frames must be at most `sample_rate / ∆f` samples apart. The hop size is
+  /// clamped to `[1, window_length]`.
+  pub fn max_hop_for_bin(&self, bin: usize) -> usize {
+    let max_hop = ((self.sample_rate as f32) / self.bandwidth_hz(bin)).floor();
+
+    (max_hop as usize).clamp(1, self.window_length)
   }
 
-  #[test]
-  fn test_cqt_params_hann_window() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    ).unwrap();
+  /// Calculate the recommended hop size, the largest one sampling the output of every bin,
+  /// the highest bin having the widest bandwidth, without aliasing. See `max_hop_for_bin`.
+  pub fn recommended_hop(&self) -> usize {
+    self.max_hop_for_bin(self.num_bins.saturating_sub(1))
+  }
 
-    let hann_window = get_hann_window(WINDOW_LENGTH).unwrap();
+  /// Diagnose the parameters, returning a warning for each wasteful or degraded condition.
+  ///
+  /// The parameters stay valid, the warnings are meant to be reported to the user:
+  ///
+  /// * Bins whose center frequency is at or above the Nyquist frequency.
+  /// * Low bins whose filter support `Q * sample_rate / f_c` exceeds the window length,
+  ///   so their filters are truncated and their resolution degraded.
+  /// * Bins spaced closer than half the frequency resolution of the window,
+  ///   `sample_rate / window_length`, which are nearly identical to their neighbours.
+  pub fn warnings(&self) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let sample_rate = self.sample_rate as f32;
+    let center_freqs = self.center_freqs();
 
-    assert_eq!(cqt_params.hann_window().len(), WINDOW_LENGTH);
-    assert_eq!(cqt_params.hann_window()[0], hann_window[0]);
-    assert_eq!(cqt_params.hann_window()[WINDOW_LENGTH - 1], hann_window[WINDOW_LENGTH - 1]);
+    let above_nyquist = center_freqs
+      .iter()
+      .filter(|&&center_freq| center_freq >= self.nyquist())
+      .count();
+    if above_nyquist > 0 {
+      warnings.push(
+        format!(
+          "{} bins are at or above the Nyquist frequency of {} Hz, lower max_freq or raise the sample rate",
+          above_nyquist,
+          self.nyquist()
+        )
+      );
+    }
+
+    let filter_support = |center_freq: f32| (self.q_factor * sample_rate) / center_freq;
+    let truncated = center_freqs
+      .iter()
+      .filter(|&&center_freq| filter_support(center_freq) > (self.window_length as f32))
+      .count();
+    if truncated > 0 {
+      let required_length = filter_support(center_freqs[0]).ceil() as usize;
+      warnings.push(
+        format!(
+          "{} low bins have filters truncated by the window of {} samples, raise min_freq or the window length to {}",
+          truncated,
+          self.window_length,
+          required_length.next_power_of_two()
+        )
+      );
+    }
+
+    let min_spacing = sample_rate / (self.window_length as f32) / 2.0;
+    let overlapping = (0..self.num_bins.saturating_sub(1))
+      .filter(|&bin| self.center_freq(bin + 1) - self.center_freq(bin) < min_spacing)
+      .count();
+    if overlapping > 0 {
+      warnings.push(
+        format!(
+          "{} bins are spaced closer than half the window resolution of {:.2} Hz and nearly identical, lower bins_per_octave or raise the window length",
+          overlapping,
+          2.0 * min_spacing
+        )
+      );
+    }
+
+    warnings
   }
 
-  #[test]
-  fn test_cqt_params_norm_factor() {
-    let cqt_params = CQTParams::new(
-      MIN_FREQ,
-      MAX_FREQ,
-      BINS_PER_OCTAVE,
-      SAMPLE_RATE,
-      WINDOW_LENGTH
-    ).unwrap();
-    let hann_window = get_hann_window(WINDOW_LENGTH).unwrap();
-    let expected_norm_factor = calculate_norm(&hann_window).unwrap();
+  /// Calculate the bandwidth in Hz of every bin of the filter bank.
+  pub fn bandwidths_hz(&self) -> Array1<f32> {
+    Array1::from_shape_fn(self.num_bins, |bin| self.bandwidth_hz(bin))
+  }
 
-    assert_eq!(cqt_params.norm_factor(), expected_norm_factor);
+  /// Enable or disable the clamping of the number of bins to `max_freq`.
+  ///
+  /// By default the number of bins covers whole octaves, `K = B * ceil(log2(f_max / f_min))`,
+  /// so the highest center frequency can exceed `max_freq`. When enabled, the number of bins
+  /// is floored instead so the highest center frequency is less than or equal to `max_freq`.
+  pub fn with_clamped_max_freq(mut self, clamp_max_freq: bool) -> Self {
+    self.clamp_max_freq = clamp_max_freq;
+    self.num_bins = self.calculated_num_bins();
+    self
+  }
+
+  /// Calculate the number of bins, clamped from the tuned center frequency of the
+  /// first bin when `clamp_max_freq` is set.
+  fn calculated_num_bins(&self) -> usize {
+    if self.clamp_max_freq {
+      let first_freq = self.min_freq * self.tuning_ratio();
+      calculate_num_bins(first_freq, self.max_freq, self.bins_per_octave, true)
+    } else {
+      calculate_num_bins(self.min_freq, self.max_freq, self.bins_per_octave, false)
+    }
   }
+
+  /// Return whether the number of bins is clamped to `max_freq`.
+  pub fn clamped_max_freq(&self) -> bool {
+    self.clamp_max_freq
+  }
+
+  /// Set the type of the analysis window.
+  ///
+  /// The window replaces the Hann window, or the custom window of `with_window`, in
+  /// `hann_window` and the normalization factor is recomputed from it.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the beta of a Kaiser window is negative or not finite.
+  pub fn with_window_type(mut self, window_type: WindowType) -> Result<Self, CQTParamsError> {
+    if let WindowType::Kaiser { beta } = window_type {
+      if !beta.is_finite() || beta < 0.0 {
+        return Err(CQTParamsError::InvalidKaiserBeta);
+      }
+    }
+
+    self.window_type = window_type;
+    self.hann_window = window_type.create_window(self.window_length);
+    self.custom_window = false;
+    self.norm_factor = calculate_norm(&self.hann_window).unwrap();
+
+    Ok(self)
+  }
+
+  /// Return the type of the analysis window.
+  pub fn window_type(&self) -> WindowType {
+    self.window_type
+  }
+
+  /// Set the sign convention of the complex exponential of the kernels.
+  ///
+  /// By default the kernels use `exp(-j * 2π * f * n / sample_rate)`. With
+  /// `PhaseConvention::Positive` the sign of the phase factors is flipped, which
+  /// conjugates the complex output while keeping the magnitudes.
+  pub fn with_phase_convention(mut self, phase_convention: PhaseConvention) -> Self {
+    // Flip the sign of the phase factors when the convention changes
+    let sign = phase_convention.sign() * self.phase_convention.sign();
+
+    self.phase_factors.mapv_inplace(|phase| sign * phase);
+    self.phase_convention = phase_convention;
+    self
+  }
+
+  /// Set the synthesis window, used instead of the analysis window to overlap-add the
+  /// frames of an inverse transform, see `Cqt::cola_gain`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `InvalidWindowLength` if the length of the window isn't `window_length`, or
+  /// `InvalidWindow` if it holds values that aren't finite.
+  pub fn with_synth_window(mut self, synth_window: Vec<f32>) -> Result<Self, CQTParamsError> {
+    if synth_window.len() != self.window_length {
+      return Err(CQTParamsError::InvalidWindowLength);
+    }
+
+    if !synth_window.iter().all(|value| value.is_finite()) {
+      return Err(CQTParamsError::InvalidWindow);
+    }
+
+    self.synth_window = Some(synth_window);
+    Ok(self)
+  }
+
+  /// Bound the number of bins, guarding against frequency ranges whose filterbank
+  /// would exceed the memory budget, e.g. with parameters from an untrusted source.
+  ///
+  /// The filterbank isn't allocated by `CQTParams`, so the check happens before `Cqt::new`
+  /// computes it. See also `CQTParamsBuilder::max_bins`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `TooManyBins` if the number of bins is greater than `cap`.
+  pub fn with_max_bins_cap(self, cap: usize) -> Result<Self, CQTParamsError> {
+    if self.num_bins > cap {
+      return Err(CQTParamsError::TooManyBins { requested: self.num_bins, cap });
+    }
+
+    Ok(self)
+  }
+
+  /// Return the synthesis window, the analysis window unless set with `with_synth_window`.
+  pub fn synth_window(&self) -> &[f32] {
+    self.synth_window.as_deref().unwrap_or(&self.hann_window)
+  }
+
+  /// Return the synthesis window set with `with_synth_window`, `None` if unset.
+  pub(crate) fn custom_synth_window(&self) -> Option<&[f32]> {
+    self.synth_window.as_deref()
+  }
+
+  /// Return the sign convention of the complex exponential of the kernels.
+  pub fn phase_convention(&self) -> PhaseConvention {
+    self.phase_convention
+  }
+
+  /// Set the placement of the frames relative to the input signal.
+  ///
+  /// By default the frames are centered, looking ahead of their start. With
+  /// `Centering::Causal` each frame only depends on past samples.
+  pub fn with_centering(mut self, centering: Centering) -> Self {
+    self.centering = centering;
+    self
+  }
+
+  /// Return the placement of the frames relative to the input signal.
+  pub fn centering(&self) -> Centering {
+    self.centering
+  }
+
+  /// Return a reference to the phase factors array.
+  pub fn phase_factors(&self) -> &Array1<f32> {
+    &self.phase_factors
+  }
+
+  /// Return a reference to the Hann window array.
+  pub fn hann_window(&self) -> &Vec<f32> {
+    &self.hann_window
+  }
+
+  /// Return whether the cosine edge taper is enabled.
+  pub fn edge_taper(&self) -> bool {
+    self.edge_taper
+  }
+
+  /// Return whether the per-bin energy normalization is enabled.
+  pub fn per_bin_norm(&self) -> bool {
+    self.filter_norm == FilterNorm::PerBin
+  }
+
+  /// Return the normalization of the complex windows of the filterbank.
+  pub fn filter_norm(&self) -> FilterNorm {
+    self.filter_norm
+  }
+
+  /// Return the threshold below which the filterbank coefficients are zeroed.
+  pub fn filterbank_threshold(&self) -> f32 {
+    self.filterbank_threshold
+  }
+
+  /// Return whether the DC offset of each frame is removed.
+  pub fn remove_dc(&self) -> bool {
+    self.remove_dc
+  }
+
+  /// Return the pre-emphasis coefficient, `None` when disabled.
+  pub fn pre_emphasis(&self) -> Option<f32> {
+    self.pre_emphasis
+  }
+
+  /// Return whether the single-sided magnitude scaling is enabled.
+  pub fn single_sided(&self) -> bool {
+    self.single_sided
+  }
+
+  /// Return whether the per-octave adaptive window lengths are enabled.
+  pub fn octave_windows(&self) -> bool {
+    self.octave_windows
+  }
+
+  /// Return whether the analysis window was supplied by the caller, see `with_window`.
+  pub fn custom_window(&self) -> bool {
+    self.custom_window
+  }
+
+  /// Return the parameters of an octave analyzed with its own window length.
+  ///
+  /// Every option is kept, but the window of the `WindowType`, the normalization factor
+  /// and the phase factors are computed for `window_length`. The frequency range isn't
+  /// validated again, so an octave holding a single bin keeps valid parameters.
+  pub(crate) fn for_octave_window_length(&self, window_length: usize) -> CQTParams {
+    let hann_window = self.window_type.create_window(window_length);
+    let norm_factor = calculate_norm(&hann_window).unwrap();
+    // Keep the sign of the phase convention
+    let sign = self.phase_convention.sign() * PhaseConvention::Negative.sign();
+    let phase_factors = get_calculated_phase_factors(window_length, self.sample_rate);
+
+    CQTParams {
+      window_length,
+      requested_window_length: window_length,
+      hann_window,
+      norm_factor,
+      phase_factors: phase_factors.mapv(|phase| sign * phase),
+      octave_windows: false,
+      synth_window: None,
+      ..*self
+    }
+  }
+
+  /// Calculate the window length used for the octave starting at `first_bin`.
+  ///
+  /// The smallest power of two greater than or equal to the theoretical support
+  /// of the lowest bin of the octave, capped at the window length.
+  pub fn octave_window_length(&self, first_bin: usize) -> usize {
+    let filter_support = (self.q_factor * (self.sample_rate as f32)) / self.center_freq(first_bin);
+
+    (filter_support.ceil() as usize).next_power_of_two().min(self.window_length)
+  }
+}
+
+/// Calculate the number of bins of the filter bank.
+///
+/// # Arguments
+///
+/// * `min_freq` - The minimum frequency in Hz, the center frequency of the first bin.
+/// * `max_freq` - The maximum frequency in Hz.
+/// * `bins_per_octave` - The number of frequency bins per octave.
+/// * `clamp_max_freq` - Whether the highest center frequency must not exceed `max_freq`.
+///
+/// # Returns
+///
+/// The number of bins, covering whole octaves unless clamped to `max_freq`.
+fn calculate_num_bins(
+  min_freq: f32,
+  max_freq: f32,
+  bins_per_octave: usize,
+  clamp_max_freq: bool
+) -> usize {
+  let octaves = (max_freq / min_freq).log2();
+
+  if clamp_max_freq {
+    // Tolerate the rounding error when max_freq falls exactly on a bin
+    (((bins_per_octave as f32) * octaves + 1e-4).floor() as usize) + 1
+  } else {
+    ((bins_per_octave as f32) * octaves.ceil()) as usize
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{ collections::HashMap, f32::consts::PI };
+
+  use approx::assert_relative_eq;
+  use hann_rs::get_hann_window;
+
+  use crate::{
+    complex_hann_window::{ get_calculated_q_factor, calculate_norm },
+    compute_cqt_filterbank,
+    compute_octave_filterbanks,
+  };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_cqt_params_ok() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    );
+
+    assert!(cqt_params.is_ok());
+  }
+
+  #[test]
+  fn test_cqt_params_q_factor() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let expected_q_factor = get_calculated_q_factor(BINS_PER_OCTAVE).unwrap();
+
+    assert_eq!(cqt_params.q_factor(), expected_q_factor);
+  }
+
+  #[test]
+  fn test_cqt_params_num_bins() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let expected_num_bins = ((BINS_PER_OCTAVE as f32) *
+      (MAX_FREQ / MIN_FREQ).log2().ceil()) as usize;
+
+    assert_eq!(cqt_params.num_bins(), expected_num_bins);
+  }
+
+  #[test]
+  fn test_cqt_params_clamped_max_freq() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let clamped_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_clamped_max_freq(true);
+
+    // 20 Hz to 7902.1 Hz spans ~8.6 octaves
+    assert_eq!(cqt_params.num_bins(), 108);
+    assert!(cqt_params.center_freq(cqt_params.num_bins() - 1) > MAX_FREQ);
+    assert!(clamped_cqt_params.clamped_max_freq());
+    assert_eq!(clamped_cqt_params.num_bins(), 104);
+    assert!(clamped_cqt_params.center_freq(clamped_cqt_params.num_bins() - 1) <= MAX_FREQ);
+    assert!(clamped_cqt_params.center_freq(clamped_cqt_params.num_bins()) > MAX_FREQ);
+
+    // A max_freq falling exactly on a bin keeps that bin
+    let max_freq = MIN_FREQ * 4.0;
+    let boundary_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      max_freq,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_clamped_max_freq(true);
+    assert_eq!(boundary_cqt_params.num_bins(), 2 * BINS_PER_OCTAVE + 1);
+  }
+
+  #[test]
+  fn test_cqt_params_clamped_max_freq_tuning() {
+    let clamped_cqt_params = |tuning_cents: f32| {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH)
+        .unwrap()
+        .with_clamped_max_freq(true)
+        .with_tuning_cents(tuning_cents)
+    };
+
+    // The last clamped bin is about 51 cents below 7902.1 Hz, a semitone up passes it
+    let cqt_params = clamped_cqt_params(100.0);
+    assert_eq!(cqt_params.num_bins(), 103);
+    assert!(cqt_params.center_freq(cqt_params.num_bins() - 1) <= MAX_FREQ);
+    assert!(cqt_params.center_freq(cqt_params.num_bins()) > MAX_FREQ);
+
+    // The clamped number of bins is the same whichever option is set first
+    let tuned_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_tuning_cents(100.0)
+      .with_clamped_max_freq(true);
+    assert_eq!(tuned_cqt_params.num_bins(), 103);
+
+    // Tuning back down restores the bin
+    let mut cqt_params = cqt_params;
+    cqt_params.set_tuning_cents(0.0);
+    assert_eq!(cqt_params.num_bins(), 104);
+  }
+
+  #[test]
+  fn test_cqt_params_center_freq() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    let expected_center_freq = MIN_FREQ * (2f32).powf(1.0 / (BINS_PER_OCTAVE as f32)).powf(40.0);
+
+    assert_eq!(cqt_params.center_freq(0), MIN_FREQ);
+    assert_eq!(cqt_params.center_freq(40), expected_center_freq);
+  }
+
+  #[test]
+  fn test_cqt_params_fft_bin_freqs() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    let fft_bin_freqs = cqt_params.fft_bin_freqs();
+
+    assert_eq!(fft_bin_freqs.len(), WINDOW_LENGTH / 2 + 1);
+    assert_eq!(fft_bin_freqs[0], 0.0);
+    assert_eq!(fft_bin_freqs[1], (SAMPLE_RATE as f32) / (WINDOW_LENGTH as f32));
+    assert_relative_eq!(fft_bin_freqs[WINDOW_LENGTH / 2], cqt_params.nyquist());
+  }
+
+  #[test]
+  fn test_cqt_params_coverage() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert_eq!(cqt_params.nyquist(), (SAMPLE_RATE as f32) / 2.0);
+
+    let (lowest_freq, highest_freq) = cqt_params.coverage();
+    assert_eq!(lowest_freq, MIN_FREQ);
+    assert_eq!(highest_freq, cqt_params.center_freq(cqt_params.num_bins() - 1));
+    assert!(highest_freq <= cqt_params.nyquist());
+  }
+
+  #[test]
+  fn test_cqt_params_rescale_sample_rate() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let expected = CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, 48000, WINDOW_LENGTH).unwrap();
+
+    let rescaled = cqt_params.rescale_sample_rate(48000).unwrap();
+    assert_eq!(rescaled.sample_rate, 48000);
+    assert_eq!(rescaled.num_bins(), cqt_params.num_bins());
+    assert_ne!(rescaled.phase_factors(), cqt_params.phase_factors());
+    assert_eq!(rescaled, expected);
+
+    // The phase convention is kept
+    let positive = cqt_params
+      .with_phase_convention(PhaseConvention::Positive)
+      .rescale_sample_rate(48000)
+      .unwrap();
+    assert_eq!(positive, expected.with_phase_convention(PhaseConvention::Positive));
+  }
+
+  #[test]
+  fn test_cqt_params_rescale_sample_rate_invalid() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert_eq!(cqt_params.rescale_sample_rate(0), Err(CQTParamsError::InvalidSampleRate));
+    assert_eq!(
+      cqt_params.rescale_sample_rate(8000),
+      Err(CQTParamsError::MaxFrequencyAboveNyquist)
+    );
+  }
+
+  #[test]
+  fn test_cqt_params_recommended_hop() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let low_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ / 4.0,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    // The highest bin samples at least its bandwidth
+    let last_bin = cqt_params.num_bins() - 1;
+    let hop_size = cqt_params.recommended_hop();
+    assert_eq!(hop_size, cqt_params.max_hop_for_bin(last_bin));
+    assert!((SAMPLE_RATE as f32) / (hop_size as f32) >= cqt_params.bandwidth_hz(last_bin));
+    assert!((SAMPLE_RATE as f32) / ((hop_size + 1) as f32) < cqt_params.bandwidth_hz(last_bin));
+
+    // A higher maximum frequency needs a smaller hop size
+    assert!(hop_size < low_cqt_params.recommended_hop());
+    assert!(hop_size < WINDOW_LENGTH / 2);
+
+    // The lowest bins are clamped to the window length
+    assert_eq!(cqt_params.max_hop_for_bin(0), WINDOW_LENGTH);
+  }
+
+  #[test]
+  fn test_cqt_params_synth_window() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+
+    // The analysis window is used by default
+    let cqt_params = new_params();
+    assert_eq!(cqt_params.synth_window(), cqt_params.hann_window().as_slice());
+
+    let cqt_params = new_params().with_synth_window(vec![1.0; WINDOW_LENGTH]).unwrap();
+    assert_eq!(cqt_params.synth_window(), vec![1.0; WINDOW_LENGTH].as_slice());
+
+    assert_eq!(
+      new_params().with_synth_window(vec![1.0; WINDOW_LENGTH / 2]),
+      Err(CQTParamsError::InvalidWindowLength)
+    );
+    assert_eq!(
+      new_params().with_synth_window(vec![f32::NAN; WINDOW_LENGTH]),
+      Err(CQTParamsError::InvalidWindow)
+    );
+  }
+
+  #[test]
+  fn test_cqt_params_max_bins_cap() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let num_bins = new_params().num_bins();
+
+    assert!(new_params().with_max_bins_cap(num_bins).is_ok());
+    assert_eq!(
+      new_params().with_max_bins_cap(num_bins - 1),
+      Err(CQTParamsError::TooManyBins { requested: num_bins, cap: num_bins - 1 })
+    );
+  }
+
+  #[test]
+  fn test_cqt_params_warnings() {
+    // The filter of a 110 Hz bin spans 6735 samples at 12 bins per octave
+    let cqt_params = CQTParams::new(110.0, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 8192).unwrap();
+    assert!(cqt_params.warnings().is_empty());
+
+    // The filter of a 100 Hz bin spans 7409 samples
+    let cqt_params = CQTParams::new(100.0, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 4096).unwrap();
+    let warnings = cqt_params.warnings();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].contains("truncated") && warnings[0].contains("8192"));
+
+    // The lowest bins are 1.2 Hz apart with a resolution of 10.8 Hz
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let warnings = cqt_params.warnings();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0].contains("truncated") && warnings[0].contains("65536"));
+    assert!(warnings[1].contains("nearly identical"));
+
+    // Whole octaves from 20 Hz reach 9.7 kHz, above the Nyquist frequency of 8 kHz
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, 16000, 2048).unwrap();
+    assert!(cqt_params.warnings()[0].contains("Nyquist"));
+  }
+
+  #[test]
+  fn test_cqt_params_config_key() {
+    let new_params = |min_freq: f32| {
+      CQTParams::new(min_freq, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+
+    assert_eq!(new_params(MIN_FREQ).config_key(), new_params(MIN_FREQ).config_key());
+    assert_ne!(new_params(MIN_FREQ).config_key(), new_params(MIN_FREQ * 2.0).config_key());
+
+    let mut cache = HashMap::new();
+    cache.insert(new_params(MIN_FREQ).config_key(), 1);
+    assert_eq!(cache.get(&new_params(MIN_FREQ).config_key()), Some(&1));
+  }
+
+  #[test]
+  fn test_cqt_params_config_key_options() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let key = new_params().config_key();
+
+    // Parameters differing only in an option get different keys
+    let option_keys = [
+      new_params().with_edge_taper(true).config_key(),
+      new_params().with_filter_norm(FilterNorm::UnityPeak).config_key(),
+      new_params().with_tuning_cents(-25.0).config_key(),
+      new_params().with_window_type(WindowType::Kaiser { beta: 8.6 }).unwrap().config_key(),
+      new_params().with_filterbank_threshold(1e-4).config_key(),
+      new_params().with_centering(Centering::Causal).config_key(),
+      new_params().with_phase_convention(PhaseConvention::Positive).config_key(),
+      new_params().with_remove_dc(true).config_key(),
+      new_params().with_pre_emphasis(Some(0.97)).config_key(),
+      new_params().with_octave_windows(true).config_key(),
+      new_params().with_single_sided(true).config_key(),
+      new_params().with_clamped_max_freq(true).config_key(),
+      new_params()
+        .with_synth_window(vec![1.0; WINDOW_LENGTH])
+        .unwrap()
+        .config_key(),
+    ];
+    for option_key in &option_keys {
+      assert_ne!(option_key, &key);
+    }
+
+    // The custom windows are compared by their samples
+    let window = get_hann_window(WINDOW_LENGTH).unwrap();
+    let with_window = |window: Vec<f32>| {
+      CQTParams::with_window(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, window).unwrap()
+    };
+    let mut scaled_window = window.clone();
+    scaled_window[WINDOW_LENGTH / 2] *= 0.5;
+    assert_eq!(with_window(window.clone()).config_key(), with_window(window.clone()).config_key());
+    assert_ne!(
+      with_window(window.clone()).config_key(),
+      with_window(scaled_window.clone()).config_key()
+    );
+    // A custom window is keyed apart from the built-in window with the same samples
+    assert_ne!(with_window(window).config_key(), key);
+
+    // The Kaiser beta and the synthesis window samples are compared exactly
+    let with_kaiser = |beta: f32| {
+      new_params().with_window_type(WindowType::Kaiser { beta }).unwrap().config_key()
+    };
+    assert_eq!(with_kaiser(8.6), with_kaiser(8.6));
+    assert_ne!(with_kaiser(8.6), with_kaiser(8.0));
+    let with_synth_window = |window: Vec<f32>| {
+      new_params().with_synth_window(window).unwrap().config_key()
+    };
+    let synth_key = with_synth_window(get_hann_window(WINDOW_LENGTH).unwrap());
+    assert_eq!(synth_key, with_synth_window(get_hann_window(WINDOW_LENGTH).unwrap()));
+    assert_ne!(synth_key, key);
+    assert_ne!(synth_key, with_synth_window(scaled_window));
+  }
+
+  #[test]
+  fn test_cqt_params_tuning_cents() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_tuning_cents(100.0);
+
+    // A 100 cents deviation shifts every bin by one semitone
+    assert_eq!(cqt_params.tuning_cents(), 100.0);
+    assert_relative_eq!(cqt_params.center_freq(0), MIN_FREQ * (2f32).powf(1.0 / 12.0));
+    assert_relative_eq!(cqt_params.center_freq(11), MIN_FREQ * 2.0, max_relative = 1e-6);
+  }
+
+  #[test]
+  fn test_cqt_params_bandwidths_hz() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let bandwidths = cqt_params.bandwidths_hz();
+    let freq_ratio = cqt_params.center_freq(1) / cqt_params.center_freq(0);
+
+    assert_eq!(bandwidths.len(), cqt_params.num_bins());
+    assert_eq!(bandwidths[0], cqt_params.bandwidth_hz(0));
+    assert_eq!(cqt_params.bandwidth_hz(0), MIN_FREQ / cqt_params.q_factor());
+
+    // The bandwidth grows geometrically, at the same ratio as the center frequencies
+    for bin in 1..bandwidths.len() {
+      assert_relative_eq!(bandwidths[bin] / bandwidths[bin - 1], freq_ratio, max_relative = 1e-4);
+    }
+  }
+
+  #[test]
+  fn test_cqt_params_bin_overlap() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let freq_ratio = cqt_params.center_freq(1) / cqt_params.center_freq(0);
+    let expected_overlap = 1.0 / (cqt_params.q_factor() * (freq_ratio - 1.0));
+
+    // The overlap is the same for every bin of a constant-Q filter bank
+    for bin in 0..cqt_params.num_bins() {
+      assert_relative_eq!(cqt_params.bin_overlap(bin), expected_overlap, max_relative = 1e-3);
+    }
+  }
+
+  #[test]
+  fn test_cqt_params_phase_factors() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert_eq!(cqt_params.phase_factors().len(), WINDOW_LENGTH);
+    assert_eq!(cqt_params.phase_factors()[0], 0.0);
+    assert_eq!(
+      cqt_params.phase_factors()[WINDOW_LENGTH - 1],
+      (-2.0 * PI * ((WINDOW_LENGTH - 1) as f32)) / (SAMPLE_RATE as f32)
+    );
+  }
+
+  #[test]
+  fn test_cqt_params_hann_window() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    let hann_window = get_hann_window(WINDOW_LENGTH).unwrap();
+
+    assert_eq!(cqt_params.hann_window().len(), WINDOW_LENGTH);
+    assert_eq!(cqt_params.hann_window()[0], hann_window[0]);
+    assert_eq!(cqt_params.hann_window()[WINDOW_LENGTH - 1], hann_window[WINDOW_LENGTH - 1]);
+  }
+
+  #[test]
+  fn test_cqt_params_norm_factor() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let hann_window = get_hann_window(WINDOW_LENGTH).unwrap();
+    let expected_norm_factor = calculate_norm(&hann_window).unwrap();
+
+    assert_eq!(cqt_params.norm_factor(), expected_norm_factor);
+  }
+
+  #[test]
+  fn test_cqt_params_shared_norm_factor() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let other_cqt_params = CQTParams::new(30.0, 4000.0, 24, 22050, WINDOW_LENGTH).unwrap();
+
+    assert_eq!(cqt_params.norm_factor(), other_cqt_params.norm_factor());
+  }
+
+  #[test]
+  fn test_cqt_params_effective_window_length() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, 4000).unwrap();
+
+    assert_eq!(cqt_params.requested_window_length(), 4000);
+    assert_eq!(cqt_params.effective_window_length(), 4096);
+    assert_eq!(cqt_params.hann_window().len(), cqt_params.effective_window_length());
+    assert_eq!(cqt_params.phase_factors().len(), cqt_params.effective_window_length());
+  }
+
+  #[test]
+  fn test_cqt_params_octave_window_length() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert!(!cqt_params.octave_windows());
+    assert_eq!(cqt_params.octave_window_length(0), WINDOW_LENGTH);
+    // 20 Hz * 2^7 = 2560 Hz has a support of ~290 samples
+    assert_eq!(cqt_params.octave_window_length(7 * BINS_PER_OCTAVE), 512);
+  }
+
+  #[test]
+  fn test_cqt_params_edge_taper() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert!(!cqt_params.edge_taper());
+    assert!(cqt_params.with_edge_taper(true).edge_taper());
+  }
+
+  #[test]
+  fn test_cqt_params_per_bin_norm() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert!(!cqt_params.per_bin_norm());
+    assert_eq!(cqt_params.filter_norm(), FilterNorm::Global);
+
+    let cqt_params = cqt_params.with_per_bin_norm(true);
+    assert!(cqt_params.per_bin_norm());
+    assert_eq!(cqt_params.filter_norm(), FilterNorm::PerBin);
+
+    let cqt_params = cqt_params.with_filter_norm(FilterNorm::UnityPeak);
+    assert!(!cqt_params.per_bin_norm());
+    assert_eq!(cqt_params.filter_norm(), FilterNorm::UnityPeak);
+  }
+
+  #[test]
+  fn test_cqt_params_with_window() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let window_cqt_params = CQTParams::with_window(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      get_hann_window(WINDOW_LENGTH).unwrap()
+    ).unwrap();
+
+    // The standard Hann window reproduces the filterbank of `new`
+    let filterbank = compute_cqt_filterbank(&cqt_params).unwrap();
+    assert_eq!(compute_cqt_filterbank(&window_cqt_params).unwrap(), filterbank);
+
+    // The window length isn't rounded up to a power of two
+    let window = vec![1.0; 4000];
+    let window_cqt_params = CQTParams::with_window(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      window.clone()
+    ).unwrap();
+    assert_eq!(window_cqt_params.effective_window_length(), 4000);
+    assert_eq!(window_cqt_params.hann_window(), &window);
+    assert_eq!(window_cqt_params.phase_factors().len(), 4000);
+    assert_eq!(window_cqt_params.norm_factor(), 1.0);
+    assert!(window_cqt_params.custom_window());
+
+    // A window type replaces the custom window, which the octave windows then accept
+    let window_cqt_params = window_cqt_params
+      .with_window_type(WindowType::Hann)
+      .unwrap()
+      .with_octave_windows(true);
+    assert!(!window_cqt_params.custom_window());
+    assert_eq!(window_cqt_params.hann_window(), &get_hann_window(4000).unwrap());
+    assert!(compute_octave_filterbanks(&window_cqt_params).is_ok());
+  }
+
+  #[test]
+  fn test_cqt_params_with_window_invalid() {
+    let cqt_params = CQTParams::with_window(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      vec![]
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidWindowLength));
+
+    let cqt_params = CQTParams::with_window(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      vec![0.5, f32::NAN, 0.5]
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidWindow));
+  }
+
+  #[test]
+  fn test_cqt_params_phase_convention() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    assert_eq!(cqt_params.phase_convention(), PhaseConvention::Negative);
+
+    let phase_factors = cqt_params.phase_factors().clone();
+    let cqt_params = cqt_params.with_phase_convention(PhaseConvention::Positive);
+    assert_eq!(cqt_params.phase_convention(), PhaseConvention::Positive);
+    assert_eq!(cqt_params.phase_factors(), &phase_factors.mapv(|phase| -phase));
+
+    // Setting the same convention again keeps the phase factors
+    let cqt_params = cqt_params
+      .with_phase_convention(PhaseConvention::Positive)
+      .with_phase_convention(PhaseConvention::Negative);
+    assert_eq!(cqt_params.phase_factors(), &phase_factors);
+  }
+
+  #[test]
+  fn test_cqt_params_filterbank_threshold() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert_eq!(cqt_params.filterbank_threshold(), 0.0);
+    assert_eq!(cqt_params.with_filterbank_threshold(1e-4).filterbank_threshold(), 1e-4);
+  }
+
+  #[test]
+  fn test_cqt_params_remove_dc() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert!(!cqt_params.remove_dc());
+    assert!(cqt_params.with_remove_dc(true).remove_dc());
+  }
+
+  #[test]
+  fn test_cqt_params_pre_emphasis() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert_eq!(cqt_params.pre_emphasis(), None);
+    assert_eq!(cqt_params.with_pre_emphasis(Some(0.97)).pre_emphasis(), Some(0.97));
+  }
+
+  #[test]
+  fn test_cqt_params_kaiser_window() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    assert_eq!(cqt_params.window_type(), WindowType::Hann);
+
+    let window_type = WindowType::Kaiser { beta: 8.0 };
+    let cqt_params = cqt_params.with_window_type(window_type).unwrap();
+    let kaiser_window = window_type.create_window(WINDOW_LENGTH);
+
+    assert_eq!(cqt_params.window_type(), window_type);
+    assert_eq!(cqt_params.hann_window(), &kaiser_window);
+    assert_eq!(cqt_params.norm_factor(), calculate_norm(&kaiser_window).unwrap());
+  }
+
+  #[test]
+  fn test_cqt_params_invalid_kaiser_beta() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    let cqt_params = cqt_params.with_window_type(WindowType::Kaiser { beta: -1.0 });
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidKaiserBeta));
+  }
+
+  #[test]
+  fn test_cqt_params_large_kaiser_beta() {
+    // I0(1500) overflows f64, the window stays finite
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_window_type(WindowType::Kaiser { beta: 1500.0 })
+      .unwrap();
+
+    assert!(cqt_params.hann_window().iter().all(|value| value.is_finite()));
+    assert!(cqt_params.norm_factor().is_finite());
+  }
+
+  #[test]
+  fn test_cqt_params_window_length_too_large() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      usize::MAX - 1
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::WindowLengthTooLarge));
+  }
+
+  #[test]
+  fn test_cqt_params_with_n_bins() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let n_bins_params = CQTParams::with_n_bins(
+      MIN_FREQ,
+      BINS_PER_OCTAVE,
+      108,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    assert_eq!(n_bins_params.num_bins(), cqt_params.num_bins());
+    assert_eq!(n_bins_params.max_freq, n_bins_params.center_freq(107));
+    assert!(n_bins_params.clamped_max_freq());
+
+    let filterbank = compute_cqt_filterbank(&cqt_params).unwrap();
+    let n_bins_filterbank = compute_cqt_filterbank(&n_bins_params).unwrap();
+    assert_eq!(n_bins_filterbank.dim(), filterbank.dim());
+
+    // Clamping again keeps the number of bins
+    let n_bins_params = n_bins_params.with_clamped_max_freq(true);
+    assert_eq!(n_bins_params.num_bins(), 108);
+  }
+
+  #[test]
+  fn test_cqt_params_with_n_bins_invalid() {
+    let cqt_params = CQTParams::with_n_bins(
+      MIN_FREQ,
+      BINS_PER_OCTAVE,
+      0,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidNumBins));
+
+    // The last center frequency would be about 19.4 kHz at 12 bins per octave
+    let cqt_params = CQTParams::with_n_bins(MIN_FREQ, BINS_PER_OCTAVE, 120, 32000, WINDOW_LENGTH);
+    assert_eq!(cqt_params, Err(CQTParamsError::MaxFrequencyAboveNyquist));
+  }
+
+  #[test]
+  fn test_cqt_params_non_finite_frequencies() {
+    let cqt_params = CQTParams::new(
+      f32::NAN,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidMinFrequency));
+
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      f32::NAN,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidMaxFrequency));
+
+    // An infinite range would ask for usize::MAX bins
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      f32::INFINITY,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    );
+    assert_eq!(cqt_params, Err(CQTParamsError::InvalidMaxFrequency));
+  }
+
   #[test]
   fn test_cqt_params_invalid_min_frequency() {
     let cqt_params = CQTParams::new(-10.0, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH);
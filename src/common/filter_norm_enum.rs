@@ -0,0 +1,12 @@
+/// The normalization of the complex windows of the filterbank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FilterNorm {
+  /// Every window is scaled by the same global `norm_factor`.
+  #[default]
+  Global,
+  /// Each window is scaled to unit energy, compensating the differing supports of the bins.
+  PerBin,
+  /// Each window is scaled to unity gain at its center frequency, so a unit-amplitude
+  /// sine at the center frequency of a bin reads a magnitude of 1 in that bin.
+  UnityPeak,
+}
\ No newline at end of file
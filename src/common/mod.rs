@@ -1,3 +1,17 @@
 pub mod cqt_params_struct;
+pub mod cqt_params_key_struct;
+pub mod cqt_params_builder_struct;
+pub mod centering_enum;
+pub mod filter_norm_enum;
+pub mod phase_convention_enum;
+pub mod window_type_enum;
+pub mod window_rounding_enum;
 
-pub use cqt_params_struct::{ CQTParams, CQTParamsError };
\ No newline at end of file
+pub use cqt_params_struct::{ CQTParams, CQTParamsError };
+pub use cqt_params_key_struct::CQTParamsKey;
+pub use cqt_params_builder_struct::CQTParamsBuilder;
+pub use centering_enum::Centering;
+pub use filter_norm_enum::FilterNorm;
+pub use phase_convention_enum::PhaseConvention;
+pub use window_type_enum::WindowType;
+pub use window_rounding_enum::WindowRounding;
\ No newline at end of file
@@ -0,0 +1,20 @@
+/// The sign convention of the complex exponential of the CQT kernels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PhaseConvention {
+  /// The kernels turn clockwise, `exp(-j * 2π * f * n / sample_rate)`.
+  #[default]
+  Negative,
+  /// The kernels turn counterclockwise, `exp(+j * 2π * f * n / sample_rate)`, conjugating
+  /// the complex output of the transform.
+  Positive,
+}
+
+impl PhaseConvention {
+  /// Return the sign of the exponent of the kernels, `-1.0` or `1.0`.
+  pub fn sign(&self) -> f32 {
+    match self {
+      PhaseConvention::Negative => -1.0,
+      PhaseConvention::Positive => 1.0,
+    }
+  }
+}
\ No newline at end of file
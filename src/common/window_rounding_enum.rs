@@ -0,0 +1,68 @@
+/// How the requested window length is adjusted to the analysis window length.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WindowRounding {
+  /// Round up to the next power of two.
+  #[default]
+  Up,
+  /// Round to the nearest power of two, up on ties.
+  Nearest,
+  /// Keep the window length as is, even when it isn't a power of two.
+  Exact,
+}
+
+impl WindowRounding {
+  /// Adjust a window length.
+  ///
+  /// # Arguments
+  ///
+  /// * `window_length` - The requested window length.
+  ///
+  /// # Returns
+  ///
+  /// * `Option<usize>` - The adjusted window length, `None` if rounding up overflows.
+  pub fn round(&self, window_length: usize) -> Option<usize> {
+    let next_power_of_two = window_length.checked_next_power_of_two();
+
+    match self {
+      WindowRounding::Up => next_power_of_two,
+      WindowRounding::Nearest => {
+        if window_length.is_power_of_two() {
+          return Some(window_length);
+        }
+
+        // The previous power of two is always representable
+        let previous_power_of_two = 1 << window_length.ilog2();
+
+        match next_power_of_two {
+          Some(next) if next - window_length <= window_length - previous_power_of_two => Some(next),
+          _ => Some(previous_power_of_two),
+        }
+      }
+      WindowRounding::Exact => Some(window_length),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_window_rounding() {
+    assert_eq!(WindowRounding::default(), WindowRounding::Up);
+
+    assert_eq!(WindowRounding::Up.round(3000), Some(4096));
+    assert_eq!(WindowRounding::Nearest.round(3000), Some(2048));
+    assert_eq!(WindowRounding::Exact.round(3000), Some(3000));
+
+    // Powers of two are kept and ties round up
+    assert_eq!(WindowRounding::Nearest.round(4096), Some(4096));
+    assert_eq!(WindowRounding::Nearest.round(3072), Some(4096));
+    assert_eq!(WindowRounding::Nearest.round(3071), Some(2048));
+
+    // Rounding up overflows past the largest power of two, rounding down doesn't
+    let too_large = (1 << (usize::BITS - 1)) + 1;
+    assert_eq!(WindowRounding::Up.round(too_large), None);
+    assert_eq!(WindowRounding::Nearest.round(too_large), Some(1 << (usize::BITS - 1)));
+  }
+}
\ No newline at end of file
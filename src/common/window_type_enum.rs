@@ -0,0 +1,30 @@
+use hann_rs::get_hann_window;
+
+use crate::calculations::calculate_kaiser_window;
+
+/// The analysis window used by the CQT.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowType {
+  Hann,
+  Kaiser {
+    beta: f32,
+  },
+}
+
+impl WindowType {
+  /// Create the window of this type for a given window length.
+  ///
+  /// # Arguments
+  ///
+  /// * `window_length` - The length of the window.
+  ///
+  /// # Returns
+  ///
+  /// * `Vec<f32>` - The window values.
+  pub fn create_window(&self, window_length: usize) -> Vec<f32> {
+    match self {
+      WindowType::Hann => get_hann_window(window_length).unwrap(),
+      WindowType::Kaiser { beta } => calculate_kaiser_window(window_length, *beta),
+    }
+  }
+}
\ No newline at end of file
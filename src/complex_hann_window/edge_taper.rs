@@ -0,0 +1,146 @@
+use std::f32::consts::PI;
+
+/// Share of the window length used by the cosine ramp on each edge.
+///
+/// The ramps span each half of the window like the Hann window, a shorter ramp leaving
+/// the nearly flat truncated window with more leakage than the default Hann window.
+const TAPER_RATIO: usize = 2;
+
+/// Creates the central `window_length` samples of a Hann window spanning `filter_support` samples.
+///
+/// This is the theoretical window of a filter whose support exceeds the analysis window,
+/// hard truncated to the analysis window.
+///
+/// # Arguments
+///
+/// * `filter_support` - The theoretical support of the filter in samples.
+/// * `window_length` - The length of the analysis window.
+///
+/// # Returns
+///
+/// * `Vec<f32>` - The truncated Hann window.
+pub fn create_truncated_hann_window(filter_support: f32, window_length: usize) -> Vec<f32> {
+  // Offset of the analysis window inside the theoretical support
+  let offset = (filter_support - (window_length as f32)) / 2.0;
+
+  (0..window_length)
+    .map(|n| 0.5 - 0.5 * ((2.0 * PI * ((n as f32) + offset)) / (filter_support - 1.0)).cos())
+    .collect()
+}
+
+/// Tapers both edges of a window with a raised-cosine ramp in place.
+///
+/// # Arguments
+///
+/// * `window` - The window to taper.
+pub fn apply_edge_taper(window: &mut [f32]) {
+  let window_length = window.len();
+  let taper_length = window_length / TAPER_RATIO;
+
+  for n in 0..taper_length {
+    // Raised-cosine ramp going from 0 to 1 over the taper length
+    let ramp = 0.5 * (1.0 - ((PI * (n as f32)) / (taper_length as f32)).cos());
+
+    window[n] *= ramp;
+    window[window_length - 1 - n] *= ramp;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rustfft::{ FftPlanner, num_complex::Complex };
+
+  use crate::{ create_complex_hann_window, CQTParams };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+  // Support of a 20 Hz filter with 12 bins per octave at 44.1 kHz
+  const FILTER_SUPPORT: f32 = 37085.3;
+
+  // Share of the spectral energy of a filter lying outside of its main lobe
+  fn sidelobe_energy(filter: &[Complex<f32>]) -> f32 {
+    const MAIN_LOBE_HALF_WIDTH: usize = 4;
+
+    let mut spectrum = filter.to_vec();
+    FftPlanner::new().plan_fft_forward(spectrum.len()).process(&mut spectrum);
+
+    let power: Vec<f32> = spectrum
+      .iter()
+      .map(|x| x.norm_sqr())
+      .collect();
+    let peak = (0..power.len()).fold(0, |peak, k| if power[k] > power[peak] { k } else { peak });
+
+    // Sum the bins outside of the main lobe directly, their energy being tiny
+    let sidelobe: f32 = power
+      .iter()
+      .enumerate()
+      .filter(|&(k, _)| {
+        let distance = k.abs_diff(peak);
+        distance.min(power.len() - distance) > MAIN_LOBE_HALF_WIDTH
+      })
+      .map(|(_, &value)| value)
+      .sum();
+
+    sidelobe / power.iter().sum::<f32>()
+  }
+
+  #[test]
+  fn test_create_truncated_hann_window() {
+    let window = create_truncated_hann_window(FILTER_SUPPORT, WINDOW_LENGTH);
+
+    assert_eq!(window.len(), WINDOW_LENGTH);
+    assert!(window[0] > 0.9);
+    assert!(window[WINDOW_LENGTH / 2] > 0.99);
+  }
+
+  #[test]
+  fn test_apply_edge_taper() {
+    let mut window = vec![1.0; 16];
+    apply_edge_taper(&mut window);
+
+    assert_eq!(window[0], 0.0);
+    assert_eq!(window[15], 0.0);
+
+    // The ramps rise symmetrically up to the middle of the window
+    for n in 0..8 {
+      assert_eq!(window[n], window[15 - n]);
+    }
+    for n in 0..7 {
+      assert!(window[n] < window[n + 1]);
+    }
+  }
+
+  #[test]
+  fn test_edge_taper_reduces_sidelobe_energy() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let tapered_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_edge_taper(true);
+
+    // The 20 Hz filter exceeds the window, the default one uses the Hann window as is
+    let filter = create_complex_hann_window(MIN_FREQ, &cqt_params);
+    let tapered_filter = create_complex_hann_window(MIN_FREQ, &tapered_cqt_params);
+
+    assert!(
+      sidelobe_energy(tapered_filter.as_slice().unwrap()) <
+        sidelobe_energy(filter.as_slice().unwrap())
+    );
+  }
+}
\ No newline at end of file
@@ -1,19 +1,33 @@
 mod q_factor;
 mod normalization;
+mod edge_taper;
 
 use ndarray::{ Array1, Zip };
 use rustfft::num_complex::Complex;
 
 pub use q_factor::get_calculated_q_factor;
-pub use normalization::calculate_norm;
+pub use normalization::{ calculate_norm, get_calculated_norm_factor };
+pub(crate) use normalization::get_calculated_norm_factor_of;
 
-use crate::common::CQTParams;
+use edge_taper::{ apply_edge_taper, create_truncated_hann_window };
+
+use crate::common::{ CQTParams, FilterNorm, WindowType };
 
 /// Creates a window function for the Constant Q Transform (CQT) filterbank.
 ///
 /// The window function is a complex exponential multiplied by a Hann window and normalized.
 /// Formula used: W(n) = exp(-j * 2π * center_freq * Q * n / sample_rate) * norm * hann_window(n)
 ///
+/// The analysis window is the one selected by the `WindowType` of the `CQTParams`, Hann by default.
+///
+/// When the edge taper is enabled in the `CQTParams` with a Hann window and the theoretical
+/// filter support (Q * sample_rate / center_freq) exceeds the window length, the Hann window
+/// spanning the theoretical support is truncated to the window length and its edges are
+/// tapered with a cosine ramp over each half of the window instead of being hard truncated.
+///
+/// The normalization is selected by the `FilterNorm` of the `CQTParams`, see
+/// `calculate_bin_norm_factors`.
+///
 /// # Arguments
 ///
 /// * `center_freq` - The center frequency of the filter in the filterbank.
@@ -27,12 +41,16 @@ pub fn create_complex_hann_window(
   cqt_params: &CQTParams
 ) -> Array1<Complex<f32>> {
   let q_factor = cqt_params.q_factor();
-  let normalization = cqt_params.norm_factor();
+  let window_length = cqt_params.window_length;
+
+  let tapered_window = create_tapered_window(center_freq, cqt_params);
+  let hann_window = tapered_window.as_ref().unwrap_or(cqt_params.hann_window());
+  let normalization = window_norm_factor(hann_window, cqt_params);
 
   // Initialize an array of zeros for the complex window
-  let mut complex_window = Array1::zeros(cqt_params.window_length);
+  let mut complex_window = Array1::zeros(window_length);
 
-  Zip::from(cqt_params.hann_window())
+  Zip::from(hann_window)
     .and(cqt_params.phase_factors())
     .and(complex_window.view_mut())
     .par_for_each(|hann_value, phase, complex_window_element| {
@@ -47,6 +65,79 @@ pub fn create_complex_hann_window(
   complex_window
 }
 
+/// Calculates the normalization factor applied to the complex Hann window of each bin.
+///
+/// Every factor is the global `norm_factor` by default. With `FilterNorm::PerBin`, each
+/// factor gives the complex Hann window of its bin unit energy, i.e. the sum of the squared
+/// magnitudes of the window is 1. With `FilterNorm::UnityPeak`, each factor gives the bin a
+/// unity gain at its center frequency through the filterbank projection of the frames.
+///
+/// # Arguments
+///
+/// * `cqt_params` - CQTParams
+///
+/// # Returns
+///
+/// * `Array1<f32>` - The normalization factor of each bin.
+pub fn calculate_bin_norm_factors(cqt_params: &CQTParams) -> Array1<f32> {
+  cqt_params.center_freqs().mapv(|center_freq| {
+    let tapered_window = create_tapered_window(center_freq, cqt_params);
+    window_norm_factor(tapered_window.as_ref().unwrap_or(cqt_params.hann_window()), cqt_params)
+  })
+}
+
+/// Creates the tapered theoretical window of a filter when the edge taper applies to it.
+fn create_tapered_window(center_freq: f32, cqt_params: &CQTParams) -> Option<Vec<f32>> {
+  let window_length = cqt_params.window_length;
+
+  // Calculate the theoretical support of the filter
+  let filter_support = (cqt_params.q_factor() * (cqt_params.sample_rate as f32)) / center_freq;
+
+  // Taper the truncated theoretical window when the filter doesn't fit in the analysis window,
+  // a custom window being used as is
+  if
+    cqt_params.edge_taper() &&
+    cqt_params.window_type() == WindowType::Hann &&
+    !cqt_params.custom_window() &&
+    filter_support > (window_length as f32)
+  {
+    let mut window = create_truncated_hann_window(filter_support, window_length);
+    apply_edge_taper(&mut window);
+    Some(window)
+  } else {
+    None
+  }
+}
+
+/// Calculates the normalization factor of a complex window built from an analysis window.
+fn window_norm_factor(window: &[f32], cqt_params: &CQTParams) -> f32 {
+  let q_factor = cqt_params.q_factor();
+
+  match cqt_params.filter_norm() {
+    FilterNorm::Global => cqt_params.norm_factor(),
+    FilterNorm::PerBin => {
+      // The complex exponential has unit magnitude, the energy only depends on the window
+      let energy: f32 = window
+        .iter()
+        .map(|value| (q_factor * value).powi(2))
+        .sum();
+
+      1.0 / energy.sqrt()
+    }
+    FilterNorm::UnityPeak => {
+      // Projecting the spectrum of a frame on the spectrum of the window correlates them
+      // scaled by the window length, and a real sine puts half its amplitude in the bin
+      let overlap: f32 = window
+        .iter()
+        .zip(cqt_params.hann_window())
+        .map(|(value, frame_value)| value * frame_value)
+        .sum();
+
+      2.0 / ((cqt_params.window_length as f32) * q_factor * overlap)
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use approx::assert_abs_diff_eq;
@@ -87,4 +178,95 @@ mod tests {
     let complex_window = create_complex_hann_window(CENTER_FREQ, &cqt_params);
     assert_abs_diff_eq!(complex_window[0].norm(), 0.0, epsilon = TOLERANCE);
   }
+
+  #[test]
+  fn test_complex_hann_window_per_bin_norm() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let per_bin_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_edge_taper(true)
+      .with_per_bin_norm(true);
+
+    // The global normalization is shared by every bin
+    let norm_factors = calculate_bin_norm_factors(&cqt_params);
+    assert!(norm_factors.iter().all(|&factor| factor == cqt_params.norm_factor()));
+
+    // Each per-bin normalized window has unit energy
+    let per_bin_norm_factors = calculate_bin_norm_factors(&per_bin_cqt_params);
+    assert_eq!(per_bin_norm_factors.len(), per_bin_cqt_params.num_bins());
+    for center_freq in per_bin_cqt_params.center_freqs() {
+      let complex_window = create_complex_hann_window(center_freq, &per_bin_cqt_params);
+      let energy: f32 = complex_window
+        .iter()
+        .map(|x| x.norm_sqr())
+        .sum();
+
+      assert_abs_diff_eq!(energy, 1.0, epsilon = 1e-4);
+    }
+  }
+
+  #[test]
+  fn test_complex_hann_window_edge_taper() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let tapered_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_edge_taper(true);
+
+    // The bass filter doesn't fit in the window and gets the tapered theoretical window,
+    // falling off faster than the Hann window towards the edges
+    let bass_window = create_complex_hann_window(MIN_FREQ, &cqt_params);
+    let tapered_bass_window = create_complex_hann_window(MIN_FREQ, &tapered_cqt_params);
+    assert_abs_diff_eq!(tapered_bass_window[0].norm(), 0.0, epsilon = TOLERANCE);
+    assert!(tapered_bass_window[WINDOW_LENGTH / 8].norm() < bass_window[WINDOW_LENGTH / 8].norm());
+
+    // The treble filter fits in the window and is left untouched
+    let treble_window = create_complex_hann_window(MAX_FREQ, &cqt_params);
+    let tapered_treble_window = create_complex_hann_window(MAX_FREQ, &tapered_cqt_params);
+    assert_eq!(treble_window, tapered_treble_window);
+  }
+
+  #[test]
+  fn test_complex_hann_window_edge_taper_custom_window() {
+    let new_params = || {
+      CQTParams::with_window(
+        MIN_FREQ,
+        MAX_FREQ,
+        BINS_PER_OCTAVE,
+        SAMPLE_RATE,
+        vec![1.0; WINDOW_LENGTH]
+      ).unwrap()
+    };
+    let cqt_params = new_params();
+    let tapered_cqt_params = new_params().with_edge_taper(true);
+
+    // The custom window of the bass filter isn't replaced by the tapered Hann window
+    let bass_window = create_complex_hann_window(MIN_FREQ, &cqt_params);
+    let tapered_bass_window = create_complex_hann_window(MIN_FREQ, &tapered_cqt_params);
+    assert_eq!(bass_window, tapered_bass_window);
+    assert_abs_diff_eq!(tapered_bass_window[0].norm(), bass_window[WINDOW_LENGTH / 2].norm());
+  }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
-use std::{ error::Error, fmt };
-use hann_rs::get_hann_window_sum_squares;
+use std::{ collections::HashMap, error::Error, fmt, sync::Mutex };
+use hann_rs::{ get_hann_window, get_hann_window_sum_squares };
+use lazy_static::lazy_static;
 
 #[derive(Debug, PartialEq)]
 pub enum NormalizationError {
@@ -18,6 +19,76 @@ impl fmt::Display for NormalizationError {
   }
 }
 
+// Defining a lazy_static block for the CALCULATED_NORM_FACTORS
+lazy_static! {
+  // A cache of the normalization factors of the Hann window, filled on the first use of
+  // each window length.
+  pub static ref CALCULATED_NORM_FACTORS: Mutex<HashMap<usize, f32>> = Mutex::new(HashMap::new());
+}
+
+/// Retrieve the cached normalization factor of the Hann window for a given window length.
+///
+/// On the first use of a window length, the normalization factor is computed from the Hann
+/// window using the `calculate_norm` function and stored in the cache.
+///
+/// # Arguments
+///
+/// * `window_length` - The length of the Hann window.
+///
+/// # Returns
+///
+/// * Result<f32, NormalizationError> - The normalization factor.
+pub fn get_calculated_norm_factor(window_length: usize) -> Result<f32, NormalizationError> {
+  get_cached_norm_factor(window_length, || {
+    let hann_window = get_hann_window(window_length).map_err(
+      |_| NormalizationError::InvalidWindowLength
+    )?;
+    calculate_norm(&hann_window)
+  })
+}
+
+/// Retrieve the cached normalization factor of an already computed Hann window.
+///
+/// Same as `get_calculated_norm_factor`, computing the normalization factor from
+/// `hann_window` on a miss instead of creating a new Hann window.
+///
+/// # Arguments
+///
+/// * `hann_window` - A reference to the Hann window, its length being the cache key.
+///
+/// # Returns
+///
+/// * Result<f32, NormalizationError> - The normalization factor.
+pub(crate) fn get_calculated_norm_factor_of(
+  hann_window: &Vec<f32>
+) -> Result<f32, NormalizationError> {
+  get_cached_norm_factor(hann_window.len(), || calculate_norm(hann_window))
+}
+
+/// Look up the normalization factor of a window length, storing the result of `compute`
+/// on a miss. The lock isn't held while computing, so concurrent misses may compute the
+/// same factor twice.
+fn get_cached_norm_factor<F>(window_length: usize, compute: F) -> Result<f32, NormalizationError>
+  where F: FnOnce() -> Result<f32, NormalizationError>
+{
+  // The cache only holds plain values, so it stays usable if a thread panicked holding it
+  let cached = CALCULATED_NORM_FACTORS.lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .get(&window_length)
+    .copied();
+
+  if let Some(norm_factor) = cached {
+    return Ok(norm_factor);
+  }
+
+  let norm_factor = compute()?;
+  CALCULATED_NORM_FACTORS.lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .insert(window_length, norm_factor);
+
+  Ok(norm_factor)
+}
+
 /// Calculates the normalization factor for the Hann window.
 ///
 /// # Arguments
@@ -28,7 +99,7 @@ impl fmt::Display for NormalizationError {
 ///
 /// * Result<f32, NormalizationError> - The calculated normalization factor.
 pub fn calculate_norm(hann_window: &Vec<f32>) -> Result<f32, NormalizationError> {
-  if hann_window.len() == 0 {
+  if hann_window.is_empty() {
     return Err(NormalizationError::InvalidWindowLength);
   }
 
@@ -59,6 +130,36 @@ mod tests {
     assert_eq!(calculate_norm(&hann_window).unwrap(), ((1.0 / 3.0) as f32).sqrt());
   }
 
+  #[test]
+  fn test_get_calculated_norm_factor() {
+    for window_length in [1000, 4096] {
+      let hann_window = get_hann_window(window_length).unwrap();
+
+      assert_eq!(
+        get_calculated_norm_factor(window_length).unwrap(),
+        calculate_norm(&hann_window).unwrap()
+      );
+    }
+
+    assert_eq!(get_calculated_norm_factor(0), Err(NormalizationError::InvalidWindowLength));
+  }
+
+  #[test]
+  fn test_get_calculated_norm_factor_stores_miss() {
+    // A window length no other test uses
+    const WINDOW_LENGTH: usize = 1234;
+    let is_cached = || CALCULATED_NORM_FACTORS.lock().unwrap().contains_key(&WINDOW_LENGTH);
+
+    assert!(!is_cached());
+    let norm_factor = get_calculated_norm_factor(WINDOW_LENGTH).unwrap();
+    assert!(is_cached());
+    assert_eq!(CALCULATED_NORM_FACTORS.lock().unwrap()[&WINDOW_LENGTH], norm_factor);
+
+    // The factor of an already computed window shares the cache
+    let hann_window = get_hann_window(WINDOW_LENGTH).unwrap();
+    assert_eq!(get_calculated_norm_factor_of(&hann_window).unwrap(), norm_factor);
+  }
+
   #[test]
   fn test_calculate_norm_with_empty_window() {
     let hann_window = vec![];
@@ -59,7 +59,7 @@ lazy_static! {
 ///  Result<f32, QFactorError> -  The Q factor for the given number of bins per octave.
 pub fn get_calculated_q_factor(bins_per_octave: usize) -> Result<f32, QFactorError> {
   if let Some(q_factor) = Q_FACTOR_LOOKUP_TABLE.get(&bins_per_octave) {
-    Ok(q_factor.clone())
+    Ok(*q_factor)
   } else if bins_per_octave > 0 {
     // If the bins_per_octave is not in the lookup table, calculate the q factor
     Ok(calculate_q_factor(bins_per_octave))
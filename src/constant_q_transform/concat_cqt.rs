@@ -0,0 +1,232 @@
+use ndarray::{ concatenate, Array2, ArrayView2, Axis, s };
+
+/// Computes the number of frames two consecutive signal chunks must overlap by
+/// for `concat_cqt` to stitch their CQT without any padding artifact.
+///
+/// Each chunk is padded like a whole signal by `Cqt::process`, so its first and last
+/// frames overlap the zero padding. The overlap covers both the trailing frames of a
+/// chunk and the leading frames of the next one that reach into the padding.
+/// This assumes the default `Centering::Centered` frames.
+///
+/// # Arguments
+///
+/// * `window_length` - The window length of the CQT.
+/// * `hop_size` - The number of samples between successive frames.
+///
+/// # Returns
+///
+/// `usize` - The number of overlapping frames, the chunks overlapping by
+/// `overlap_frames * hop_size` samples.
+pub fn chunk_overlap_frames(window_length: usize, hop_size: usize) -> usize {
+  // Padding on each side of a chunk, rounded up
+  let half_padding = window_length.saturating_sub(hop_size).div_ceil(2);
+
+  2 * half_padding.div_ceil(hop_size)
+}
+
+/// Splits a signal into consecutive chunks overlapping as expected by `concat_cqt`.
+///
+/// Each chunk holds `chunk_len` samples, except the last one which holds the rest of the
+/// signal, and starts `overlap_frames * hop_size` samples before the end of the previous
+/// one. Processing every chunk with the same `Cqt` and hop size and concatenating them with
+/// `concat_cqt(&chunks, overlap_frames)` reproduces the CQT of the whole signal, as long as
+/// `overlap_frames` is at least `chunk_overlap_frames(window_length, hop_size)`.
+///
+/// # Arguments
+///
+/// * `signal` - The signal to split.
+/// * `chunk_len` - The number of samples of each chunk, a multiple of `hop_size`.
+/// * `overlap_frames` - The number of frames two consecutive chunks overlap by.
+/// * `hop_size` - The number of samples between successive frames.
+///
+/// # Returns
+///
+/// `Vec<(usize, &[f32])>` - The sample offset of each chunk in the signal and the chunk
+/// itself, in time order. An empty signal gives no chunk.
+///
+/// # Panics
+///
+/// Panics if `hop_size` is zero, if `chunk_len` isn't a multiple of `hop_size`, or if
+/// `chunk_len` isn't greater than the `overlap_frames * hop_size` overlapping samples.
+pub fn chunk_signal(
+  signal: &[f32],
+  chunk_len: usize,
+  overlap_frames: usize,
+  hop_size: usize
+) -> Vec<(usize, &[f32])> {
+  assert!(hop_size > 0, "The hop size must be greater than 0");
+  assert!(chunk_len % hop_size == 0, "The chunk length must be a multiple of the hop size");
+
+  let overlap_samples = overlap_frames * hop_size;
+  assert!(chunk_len > overlap_samples, "The chunk length must be greater than the overlap");
+
+  let step = chunk_len - overlap_samples;
+  let mut chunks = Vec::new();
+  let mut start = 0;
+
+  while start < signal.len() {
+    let end = (start + chunk_len).min(signal.len());
+    chunks.push((start, &signal[start..end]));
+
+    if end == signal.len() {
+      break;
+    }
+    start += step;
+  }
+
+  chunks
+}
+
+/// Concatenates the CQT of consecutive signal chunks along the time axis.
+///
+/// The chunks must be processed with the same `Cqt` and hop size. Every chunk but the
+/// last must hold a multiple of `hop_size` samples, and each chunk must start
+/// `overlap_frames * hop_size` samples before the end of the previous one, with
+/// `overlap_frames` at least `chunk_overlap_frames(window_length, hop_size)`.
+/// The overlapping frames are split between the two chunks, dropping the frames of each
+/// chunk that overlap its padding, so the result matches processing the whole signal at once.
+///
+/// # Arguments
+///
+/// * `chunks` - The CQT feature matrices of the chunks, in time order.
+/// * `overlap_frames` - The number of frames two consecutive chunks overlap by.
+///
+/// # Returns
+///
+/// `Array2<f32>` - The CQT feature matrix of the whole signal.
+///
+/// # Panics
+///
+/// Panics if the chunks don't have the same number of bins, or if a chunk has fewer
+/// frames than the frames trimmed from it.
+pub fn concat_cqt(chunks: &[Array2<f32>], overlap_frames: usize) -> Array2<f32> {
+  if chunks.is_empty() {
+    return Array2::zeros((0, 0));
+  }
+
+  // Frames dropped at the end of a chunk and at the start of the next one
+  let trailing_frames = overlap_frames / 2;
+  let leading_frames = overlap_frames - trailing_frames;
+  let last_chunk = chunks.len() - 1;
+
+  let trimmed_chunks: Vec<ArrayView2<f32>> = chunks
+    .iter()
+    .enumerate()
+    .map(|(chunk_idx, chunk)| {
+      let start = if chunk_idx == 0 { 0 } else { leading_frames };
+      let end = if chunk_idx == last_chunk {
+        chunk.nrows()
+      } else {
+        chunk.nrows() - trailing_frames
+      };
+
+      chunk.slice(s![start..end, ..])
+    })
+    .collect();
+
+  concatenate(Axis(0), &trimmed_chunks).expect("The chunks should have the same number of bins")
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_abs_diff_eq;
+  use ndarray::Zip;
+
+  use crate::{ create_dummy_audio_signal, CQTParams, Cqt };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_chunk_overlap_frames() {
+    assert_eq!(chunk_overlap_frames(WINDOW_LENGTH, 512), 8);
+    assert_eq!(chunk_overlap_frames(WINDOW_LENGTH, 1024), 4);
+    assert_eq!(chunk_overlap_frames(WINDOW_LENGTH, WINDOW_LENGTH), 0);
+  }
+
+  #[test]
+  fn test_concat_cqt() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 512;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 220.0, 2.0);
+
+    // Two chunks overlapping by the minimal number of frames
+    let overlap_frames = chunk_overlap_frames(WINDOW_LENGTH, hop_size);
+    let split = 86 * hop_size;
+    let first_chunk = cqt.process(&signal[..split], hop_size).unwrap();
+    let second_chunk = cqt.process(&signal[split - overlap_frames * hop_size..], hop_size).unwrap();
+
+    let result = concat_cqt(&[first_chunk, second_chunk], overlap_frames);
+    let expected = cqt.process(&signal, hop_size).unwrap();
+
+    assert_eq!(result.dim(), expected.dim());
+
+    Zip::from(&result)
+      .and(&expected)
+      .for_each(|&result_elem, &expected_elem| {
+        assert_abs_diff_eq!(result_elem, expected_elem, epsilon = 1e-4 * expected_elem.max(1.0));
+      });
+  }
+
+  #[test]
+  fn test_chunk_signal() {
+    let signal: Vec<f32> = (0..10).map(|sample| sample as f32).collect();
+
+    let chunks = chunk_signal(&signal, 4, 1, 2);
+
+    let offsets: Vec<usize> = chunks.iter().map(|(offset, _)| *offset).collect();
+    assert_eq!(offsets, vec![0, 2, 4, 6]);
+    assert_eq!(chunks[0].1, &signal[0..4]);
+    assert_eq!(chunks[3].1, &signal[6..10]);
+    assert!(chunk_signal(&[], 4, 1, 2).is_empty());
+  }
+
+  #[test]
+  fn test_chunk_signal_concat_cqt() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 512;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 220.0, 2.0);
+
+    let overlap_frames = chunk_overlap_frames(WINDOW_LENGTH, hop_size);
+    let chunks: Vec<Array2<f32>> = chunk_signal(&signal, 48 * hop_size, overlap_frames, hop_size)
+      .into_iter()
+      .map(|(_, chunk)| cqt.process(chunk, hop_size).unwrap())
+      .collect();
+    assert!(chunks.len() > 2);
+
+    let result = concat_cqt(&chunks, overlap_frames);
+    let expected = cqt.process(&signal, hop_size).unwrap();
+
+    assert_eq!(result.dim(), expected.dim());
+
+    Zip::from(&result)
+      .and(&expected)
+      .for_each(|&result_elem, &expected_elem| {
+        assert_abs_diff_eq!(result_elem, expected_elem, epsilon = 1e-4 * expected_elem.max(1.0));
+      });
+  }
+
+  #[test]
+  fn test_concat_cqt_no_chunk() {
+    assert_eq!(concat_cqt(&[], 8).dim(), (0, 0));
+  }
+}
\ No newline at end of file
@@ -4,6 +4,15 @@ use std::{ error::Error, fmt };
 pub enum SignalError {
   InvalidHopSize,
   EmptyInputSignal,
+  FrameLengthMismatch,
+  ChannelLengthMismatch,
+  InvalidRegion,
+  FFTError,
+  OctaveWindowsUnsupported,
+  SignalTooShortForWindow {
+    len: usize,
+    window_length: usize,
+  },
 }
 
 impl Error for SignalError {}
@@ -21,6 +30,29 @@ impl fmt::Display for SignalError {
       SignalError::EmptyInputSignal => {
         write!(f, "Empty input signal: the input signal should not be empty.")
       }
+      SignalError::FrameLengthMismatch => {
+        write!(f, "Frame length mismatch: the frame length should be equal to the window length.")
+      }
+      SignalError::ChannelLengthMismatch => {
+        write!(f, "Channel length mismatch: the channels should have the same length.")
+      }
+      SignalError::InvalidRegion => {
+        write!(f, "Invalid region: the region should lie within the input signal.")
+      }
+      SignalError::FFTError => {
+        write!(f, "FFT error: the frame could not be transformed.")
+      }
+      SignalError::OctaveWindowsUnsupported => {
+        write!(f, "Octave windows unsupported: the per-octave windows should be disabled.")
+      }
+      SignalError::SignalTooShortForWindow { len, window_length } => {
+        write!(
+          f,
+          "Signal too short: the signal of {} samples is shorter than the window length of {}.",
+          len,
+          window_length
+        )
+      }
     }
   }
 }
\ No newline at end of file
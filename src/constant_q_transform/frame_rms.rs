@@ -0,0 +1,73 @@
+use ndarray::{ Array1, s };
+
+use crate::Centering;
+
+use super::{ input_signal::pad_input_signal, SignalError };
+
+/// Computes the root mean square (RMS) of each frame of a signal.
+///
+/// The signal is padded and framed exactly like in `Cqt::process` with the default
+/// centered frames, so the RMS of frame `k` can be used to gate the CQT frame `k`,
+/// e.g. to skip silent regions.
+///
+/// # Arguments
+///
+/// * `signal` - The input signal as a slice of `f32` values.
+/// * `window_length` - The length of the frames.
+/// * `hop_size` - The number of samples between successive frames.
+///
+/// # Returns
+///
+/// `Result<Array1<f32>, SignalError>` containing the RMS of each frame.
+pub fn frame_rms(
+  signal: &[f32],
+  window_length: usize,
+  hop_size: usize
+) -> Result<Array1<f32>, SignalError> {
+  // Assign the input signal to the center of the padded signal, validating the hop size and signal
+  let signal_padded = pad_input_signal(signal, window_length, hop_size, Centering::Centered)?;
+  let num_frames = signal.len() / hop_size;
+
+  Ok(
+    Array1::from_shape_fn(num_frames, |frame_idx| {
+      let start = frame_idx * hop_size;
+      let end = start + window_length;
+      let frame = signal_padded.slice(s![start..end]);
+
+      (frame.mapv(|x| x * x).sum() / (window_length as f32)).sqrt()
+    })
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WINDOW_LENGTH: usize = 1024;
+  const HOP_SIZE: usize = 512;
+
+  #[test]
+  fn test_frame_rms_step() {
+    // A silent first half followed by a full scale square wave
+    let signal: Vec<f32> = (0..16 * HOP_SIZE)
+      .map(|n| if n < 8 * HOP_SIZE { 0.0 } else if n % 2 == 0 { 1.0 } else { -1.0 })
+      .collect();
+
+    let rms = frame_rms(&signal, WINDOW_LENGTH, HOP_SIZE).unwrap();
+    assert_eq!(rms.len(), 16);
+
+    // Frames lying fully in each half are silent or at full scale
+    assert!(rms.slice(s![..7]).iter().all(|&value| value == 0.0));
+    assert!(rms.slice(s![9..15]).iter().all(|&value| value == 1.0));
+    // The frame straddling the midpoint is in between
+    assert!(rms[8] > 0.0 && rms[8] < 1.0);
+  }
+
+  #[test]
+  fn test_frame_rms_invalid_hop_size() {
+    let signal = vec![0.0; 16];
+    let result = frame_rms(&signal, WINDOW_LENGTH, 0);
+
+    assert_eq!(result, Err(SignalError::InvalidHopSize));
+  }
+}
\ No newline at end of file
@@ -1,14 +1,20 @@
 use ndarray::{ Array1, s };
 
+use crate::Centering;
+
 use super::SignalError;
 
-/// Pads an input signal symmetrically to prepare it for the CQT computation.
+/// Pads an input signal to prepare it for the CQT computation.
+///
+/// The signal is padded with `window_len - hop_size` zeros, split between both sides
+/// when centered or all before the signal when causal.
 ///
 /// # Arguments
 ///
 /// * `signal` - The input signal as a slice of `f32` values.
 /// * `window_len` - The length of the window used in the CQT computation.
 /// * `hop_size` - The number of samples between successive CQT frames.
+/// * `centering` - The placement of the frames relative to the signal.
 ///
 /// # Returns
 ///
@@ -16,7 +22,8 @@ use super::SignalError;
 pub fn pad_input_signal(
   signal: &[f32],
   window_len: usize,
-  hop_size: usize
+  hop_size: usize,
+  centering: Centering
 ) -> Result<Array1<f32>, SignalError> {
   if hop_size == 0 || hop_size > window_len {
     return Err(SignalError::InvalidHopSize);
@@ -29,20 +36,43 @@ pub fn pad_input_signal(
 
   // Calculate the total amount of padding needed
   let signal_padding = window_len - hop_size;
-  // Calculate the amount of padding for each side of the signal
-  let half_signal_padding = signal_padding / 2;
+  // Calculate the amount of padding before the signal
+  let front_padding = centering.front_padding(window_len, hop_size);
 
   let signal_array = Array1::from(signal.to_vec());
   let mut signal_padded = Array1::<f32>::zeros(signal_padding + signal_len);
 
-  // Assign the input signal to the center of the padded signal
+  // Assign the input signal after the front padding
   signal_padded
-    .slice_mut(s![half_signal_padding..half_signal_padding + signal_len])
+    .slice_mut(s![front_padding..front_padding + signal_len])
     .assign(&signal_array);
 
   Ok(signal_padded)
 }
 
+/// Applies a pre-emphasis filter to an input signal.
+///
+/// The signal is filtered with `y[n] = x[n] - alpha * x[n - 1]`, the sample before the
+/// signal `x[-1]` being 0.
+///
+/// # Arguments
+///
+/// * `signal` - The input signal as a slice of `f32` values.
+/// * `alpha` - The pre-emphasis coefficient.
+///
+/// # Returns
+///
+/// `Vec<f32>` containing the filtered signal, of the same length as the input.
+pub fn pre_emphasize(signal: &[f32], alpha: f32) -> Vec<f32> {
+  let previous_samples = std::iter::once(0.0).chain(signal.iter().copied());
+
+  signal
+    .iter()
+    .zip(previous_samples)
+    .map(|(&sample, previous_sample)| sample - alpha * previous_sample)
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -54,15 +84,38 @@ mod tests {
   fn test_pad_input_signal_valid() {
     let hop_size = 2;
     let expected = Array1::from(vec![0.0, 1.0, 2.0, 3.0, 4.0, 0.0]);
-    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size).unwrap();
+    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size, Centering::Centered).unwrap();
+    assert_eq!(result, expected);
+  }
+
+  #[test]
+  fn test_pad_input_signal_causal() {
+    let hop_size = 2;
+    let expected = Array1::from(vec![0.0, 0.0, 1.0, 2.0, 3.0, 4.0]);
+    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size, Centering::Causal).unwrap();
     assert_eq!(result, expected);
   }
 
+  #[test]
+  fn test_pad_input_signal_hop_size_equal_to_window_len() {
+    for centering in [Centering::Centered, Centering::Causal] {
+      let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, WINDOW_LENGTH, centering).unwrap();
+      assert_eq!(result, Array1::from(SIGNAL.to_vec()));
+    }
+  }
+
+  #[test]
+  fn test_pre_emphasize() {
+    assert_eq!(pre_emphasize(&SIGNAL, 0.5), vec![1.0, 1.5, 2.0, 2.5]);
+    assert_eq!(pre_emphasize(&SIGNAL, 0.0), SIGNAL.to_vec());
+    assert!(pre_emphasize(&[], 0.5).is_empty());
+  }
+
   #[test]
   fn test_pad_input_signal_empty_signal() {
     let signal: Vec<f32> = vec![];
     let hop_size = 2;
-    let result = pad_input_signal(&signal, WINDOW_LENGTH, hop_size);
+    let result = pad_input_signal(&signal, WINDOW_LENGTH, hop_size, Centering::Centered);
 
     assert!(result.is_err());
     assert_eq!(result, Err(SignalError::EmptyInputSignal));
@@ -71,7 +124,7 @@ mod tests {
   #[test]
   fn test_pad_input_signal_invalid_hop_size_zero() {
     let hop_size = 0;
-    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size);
+    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size, Centering::Centered);
     assert!(result.is_err());
     assert_eq!(result, Err(SignalError::InvalidHopSize));
   }
@@ -79,7 +132,7 @@ mod tests {
   #[test]
   fn test_pad_input_signal_invalid_hop_size_greater_than_window_len() {
     let hop_size = 5;
-    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size);
+    let result = pad_input_signal(&SIGNAL, WINDOW_LENGTH, hop_size, Centering::Centered);
     assert!(result.is_err());
     assert_eq!(result, Err(SignalError::InvalidHopSize));
   }
@@ -1,142 +1,3207 @@
 mod input_signal;
 mod cqt_signal_error_enum;
+mod frame_rms;
+mod concat_cqt;
+mod validate_frame;
+mod reassignment;
 
 use ndarray::{
+  linalg::general_mat_mul,
   parallel::prelude::{ IntoParallelIterator, IndexedParallelIterator, ParallelIterator },
+  Array1,
   Array2,
+  ArrayView1,
+  ArrayView2,
   Axis,
   Zip,
   s,
 };
 use rustfft::{ num_complex::{ Complex, ComplexFloat }, FftPlanner };
+use std::{
+  borrow::Cow,
+  f32::consts::PI,
+  fs::File,
+  io::{ self, BufWriter, Write },
+  path::Path,
+  sync::OnceLock,
+};
+
+use crate::{
+  features::{ fold_to_chroma, NUM_PITCH_CLASSES },
+  CQTParams,
+  PhaseConvention,
+  CQTFilterbankError,
+  OctaveFilterbank,
+  compute_cqt_filterbank,
+  compute_cqt_filterbank_with_planner,
+  compute_octave_filterbanks,
+  compute_octave_filterbanks_with_planner,
+};
+use input_signal::{ pad_input_signal, pre_emphasize };
+use reassignment::ReassignmentFilterbanks;
+
+pub use cqt_signal_error_enum::SignalError;
+pub use frame_rms::frame_rms;
+pub use concat_cqt::{ chunk_overlap_frames, chunk_signal, concat_cqt };
+pub use validate_frame::validate_frame;
+
+/// The windowed frames and the real and imaginary parts of the filterbank returned by
+/// `Cqt::export_frames`.
+pub type ExportedFrames = (Array2<f32>, Array2<f32>, Array2<f32>);
+
+/// The number of elements of a CQT matrix from which its magnitudes are computed in parallel.
+///
+/// Below it, the overhead of spawning the rayon tasks outweighs the gain, see the
+/// `bench_magnitudes` benchmark.
+const PARALLEL_MAGNITUDE_THRESHOLD: usize = 1 << 16;
+
+/// The number of bins of a filterbank from which it is projected in parallel tiles.
+const PARALLEL_PROJECTION_BINS: usize = 192;
+
+/// The number of bins of each tile of the parallel filterbank projection.
+const PROJECTION_TILE_BINS: usize = 32;
+
+/// The number of frames transformed at once by `stream_chroma`.
+const STREAM_CHUNK_FRAMES: usize = 64;
+
+/// A sample of the frames fed to the FFT, either real or complex.
+trait FrameSample: Copy + Send + Sync {
+  /// Convert the sample to the complex input of the FFT.
+  fn to_complex(self) -> Complex<f32>;
+}
+
+impl FrameSample for f32 {
+  fn to_complex(self) -> Complex<f32> {
+    Complex::new(self, 0.0)
+  }
+}
+
+impl FrameSample for Complex<f32> {
+  fn to_complex(self) -> Complex<f32> {
+    self
+  }
+}
+
+/// The Cqt struct is an implementation of the Constant Q Transform (CQT)
+/// for time-frequency analysis of a signal. The struct provides methods to
+/// initialize the CQT parameters and compute the CQT of a given input signal.
+///
+/// `Cqt` is `Send + Sync`: it is immutable once constructed and every processing
+/// method only keeps its scratch buffers and FFT plans in per-call locals. A single
+/// instance can be shared across threads by wrapping it in an `Arc`:
+///
+/// ```
+/// use std::{ sync::Arc, thread };
+/// use cqt_rs::{ CQTParams, Cqt };
+///
+/// let cqt_params = CQTParams::new(20.0, 7902.1, 12, 44100, 4096).unwrap();
+/// let cqt = Arc::new(Cqt::new(cqt_params));
+///
+/// let handles: Vec<_> = (0..2)
+///   .map(|_| {
+///     let cqt = Arc::clone(&cqt);
+///     thread::spawn(move || cqt.process(&vec![0.0; 4096], 2048).unwrap())
+///   })
+///   .collect();
+///
+/// for handle in handles {
+///   assert_eq!(handle.join().unwrap().dim(), (2, 108));
+/// }
+/// ```
+pub struct Cqt {
+  cqt_params: CQTParams,
+  filterbank: Array2<Complex<f32>>,
+  octave_filterbanks: Option<Vec<OctaveFilterbank>>,
+  coherent_gains: OnceLock<Array1<f32>>,
+  reassignment_filterbanks: OnceLock<Vec<ReassignmentFilterbanks>>,
+}
+
+impl Cqt {
+  /// Constructs a new `Cqt` instance with the given parameters.
+  ///
+  /// # Arguments
+  ///
+  /// * `cqt_params` - CQTParams
+  ///
+  /// # Returns
+  ///
+  /// A new `Cqt` instance with the specified parameters.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the CQT filterbank can't be computed, see `try_new` for a non-panicking version.
+  pub fn new(cqt_params: CQTParams) -> Self {
+    Cqt::try_new(cqt_params).expect("Error computing CQT filterbank")
+  }
+
+  /// Constructs a new `Cqt` instance with the given parameters, without panicking.
+  ///
+  /// # Arguments
+  ///
+  /// * `cqt_params` - CQTParams
+  ///
+  /// # Returns
+  ///
+  /// A new `Cqt` instance with the specified parameters.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank.
+  pub fn try_new(cqt_params: CQTParams) -> Result<Self, CQTFilterbankError> {
+    Cqt::with_planner(cqt_params, &mut FftPlanner::new())
+  }
+
+  /// Constructs a new `Cqt` instance, planning the filterbank FFTs with the given planner.
+  ///
+  /// # Arguments
+  ///
+  /// * `cqt_params` - CQTParams
+  /// * `planner` - The FFT planner shared with the rest of the application
+  ///
+  /// # Returns
+  ///
+  /// A new `Cqt` instance with the specified parameters.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank.
+  pub fn with_planner(
+    cqt_params: CQTParams,
+    planner: &mut FftPlanner<f32>
+  ) -> Result<Self, CQTFilterbankError> {
+    // Compute the CQT filterbank using the CQTParams instance
+    let filterbank = compute_cqt_filterbank_with_planner(&cqt_params, planner)?;
+    // Compute the per-octave filterbanks when the adaptive window lengths are enabled
+    let octave_filterbanks = if cqt_params.octave_windows() {
+      Some(compute_octave_filterbanks_with_planner(&cqt_params, planner)?)
+    } else {
+      None
+    };
+
+    // Return a new Cqt instance with the given parameters and filterbank
+    Ok(Cqt {
+      cqt_params,
+      filterbank,
+      octave_filterbanks,
+      coherent_gains: OnceLock::new(),
+      reassignment_filterbanks: OnceLock::new(),
+    })
+  }
+
+  /// Constructs several `Cqt` instances sharing a single FFT planner.
+  ///
+  /// The instances with the same window length reuse the same FFT plan, e.g. for a
+  /// multi-resolution analysis with different numbers of bins per octave.
+  ///
+  /// # Arguments
+  ///
+  /// * `params` - The CQTParams of each instance
+  ///
+  /// # Returns
+  ///
+  /// The `Cqt` instances, in the order of their parameters.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a CQT filterbank can't be computed, like `new`.
+  pub fn build_many(params: Vec<CQTParams>) -> Vec<Cqt> {
+    let mut planner = FftPlanner::new();
+
+    params
+      .into_iter()
+      .map(|cqt_params| {
+        Cqt::with_planner(cqt_params, &mut planner).expect("Error computing CQT filterbank")
+      })
+      .collect()
+  }
+
+  /// Change the tuning of the transform, recomputing only its filterbanks.
+  ///
+  /// The CQTParams, with their Hann window and phase factors, are reused. When the number
+  /// of bins is clamped to `max_freq`, it is recomputed for the new tuning and the filterbanks
+  /// are rebuilt with the new number of bins.
+  ///
+  /// # Arguments
+  ///
+  /// * `tuning_cents` - The tuning deviation in cents applied to every center frequency
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank,
+  /// in which case the transform keeps its previous tuning.
+  pub fn retune(&mut self, tuning_cents: f32) -> Result<(), CQTFilterbankError> {
+    let previous_tuning_cents = self.cqt_params.tuning_cents();
+    self.cqt_params.set_tuning_cents(tuning_cents);
+
+    let filterbanks = compute_cqt_filterbank(&self.cqt_params).and_then(|filterbank| {
+      let octave_filterbanks = if self.cqt_params.octave_windows() {
+        Some(compute_octave_filterbanks(&self.cqt_params)?)
+      } else {
+        None
+      };
+
+      Ok((filterbank, octave_filterbanks))
+    });
+
+    match filterbanks {
+      Ok((filterbank, octave_filterbanks)) => {
+        self.filterbank = filterbank;
+        self.octave_filterbanks = octave_filterbanks;
+        // The coherent gains and the reassignment filterbanks of the previous tuning are
+        // computed again on the next use
+        self.coherent_gains = OnceLock::new();
+        self.reassignment_filterbanks = OnceLock::new();
+        Ok(())
+      }
+      Err(error) => {
+        self.cqt_params.set_tuning_cents(previous_tuning_cents);
+        Err(error)
+      }
+    }
+  }
+
+  /// Return a reference to the CQTParams of the transform.
+  pub fn cqt_params(&self) -> &CQTParams {
+    &self.cqt_params
+  }
+
+  /// Return a reference to the CQT filterbank of shape `(num_bins, window_length)`.
+  ///
+  /// The filterbank is read-only: the coherent gains of `process_dbfs` are cached from it,
+  /// so it only changes through `retune` or by building a new transform.
+  pub fn filterbank(&self) -> &Array2<Complex<f32>> {
+    &self.filterbank
+  }
+
+  /// Process the input signal and compute the Constant-Q Transform (CQT) features.
+  ///
+  /// The frames are transformed in parallel, but the output is deterministic: row `k`
+  /// always holds frame `k`, starting at sample `k * hop_size - front_padding` of the signal
+  /// (see `Centering::front_padding`).
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  pub fn process(&self, signal: &[f32], hop_size: usize) -> Result<Array2<f32>, SignalError> {
+    self.process_with(signal, hop_size, true)
+  }
+
+  /// Process the input signal and compute the Constant-Q Transform (CQT) features,
+  /// optionally skipping the Hann window.
+  ///
+  /// Without the window, the raw frames are transformed with a rectangular window before
+  /// going through the filterbank, which is useful to compare against analytic DFT results.
+  /// An all-zero signal short-circuits to a zero matrix without computing any FFT.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  /// * `window` - Whether to apply the Hann window to each frame
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  pub fn process_with(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    window: bool
+  ) -> Result<Array2<f32>, SignalError> {
+    let window_len = self.cqt_params.window_length;
+    let valid_input = !signal.is_empty() && hop_size > 0 && hop_size <= window_len;
+
+    // Skip the FFTs of a silent signal, its CQT being all zeros
+    if valid_input && signal.iter().all(|&sample| sample == 0.0) {
+      return Ok(Array2::<f32>::zeros((signal.len() / hop_size, self.filterbank.nrows())));
+    }
+
+    let cqt_filtered = self.process_complex_with(signal, hop_size, window)?;
+
+    // Compute the element-wise absolute value of the filtered CQT matrix
+    let mut abs_cqt_filtered = magnitudes(&cqt_filtered);
+    self.scale_magnitudes(&mut abs_cqt_filtered);
+
+    Ok(abs_cqt_filtered)
+  }
+
+  /// Process a 16-bit PCM input signal and compute the Constant-Q Transform (CQT) features.
+  ///
+  /// The samples are normalized to `[-1, 1)` by dividing them by 32768.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - The 16-bit PCM samples of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  pub fn process_i16(&self, signal: &[i16], hop_size: usize) -> Result<Array2<f32>, SignalError> {
+    let signal: Vec<f32> = signal
+      .iter()
+      .map(|&sample| (sample as f32) / 32768.0)
+      .collect();
+
+    self.process(&signal, hop_size)
+  }
+
+  /// Process a complex input signal given as planar real and imaginary parts and
+  /// compute the Constant-Q Transform (CQT) features.
+  ///
+  /// The parts are zipped into complex frames, transformed with a single FFT and projection
+  /// pass. The single-sided scaling isn't applied: it restores the energy of the mirror image
+  /// of a real signal, which a complex signal doesn't have.
+  ///
+  /// # Arguments
+  ///
+  /// * `re` - The real part of the input signal
+  /// * `im` - The imaginary part of the input signal, of the same length as `re`
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  pub fn process_planar_complex(
+    &self,
+    re: &[f32],
+    im: &[f32],
+    hop_size: usize
+  ) -> Result<Array2<f32>, SignalError> {
+    if re.len() != im.len() {
+      return Err(SignalError::ChannelLengthMismatch);
+    }
+
+    let window_len = self.cqt_params.window_length;
+    let centering = self.cqt_params.centering();
+
+    // Pad both parts like a real signal, validating the hop size and signal
+    let re_padded = pad_input_signal(&self.pre_emphasized(re), window_len, hop_size, centering)?;
+    let im_padded = pad_input_signal(&self.pre_emphasized(im), window_len, hop_size, centering)?;
+    let signal_padded = Zip::from(&re_padded)
+      .and(&im_padded)
+      .map_collect(|&re_elem, &im_elem| Complex::new(re_elem, im_elem));
+    let num_frames = re.len() / hop_size;
+
+    let cqt_filtered = self.compute_cqt_frames(num_frames, true, |frame_idx| {
+      let start = frame_idx * hop_size;
+
+      signal_padded.slice(s![start..start + window_len])
+    })?;
+
+    Ok(magnitudes(&cqt_filtered))
+  }
+
+  /// Process the analytic signal of the input signal and compute its Constant-Q
+  /// Transform (CQT) features.
+  ///
+  /// The analytic signal, computed with the FFT-based Hilbert transform of the whole signal,
+  /// only holds the frequencies the filters are centered on, so the mirror image of each
+  /// component at the opposite frequency doesn't leak into the filters. This matters for
+  /// the low bins, whose filters are wide enough to reach the mirror image.
+  /// The analytic signal holds the energy of both sides, so the magnitudes are about twice
+  /// the `process` ones, as with the single-sided scaling, which isn't applied on top.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  pub fn process_analytic(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<Array2<f32>, SignalError> {
+    if signal.is_empty() {
+      return Err(SignalError::EmptyInputSignal);
+    }
+
+    let (re, mut im) = analytic_signal(signal);
+
+    // The filters sit at the negative frequencies with the default phase convention
+    if self.cqt_params.phase_convention() == PhaseConvention::Negative {
+      im.iter_mut().for_each(|sample| *sample = -*sample);
+    }
+
+    self.process_planar_complex(&re, &im, hop_size)
+  }
+
+  /// Process each channel of a planar multichannel signal and compute its
+  /// Constant-Q Transform (CQT) features.
+  ///
+  /// The channels are processed in parallel.
+  ///
+  /// # Arguments
+  ///
+  /// * `channels` - An ArrayView2<f32> of shape `(num_channels, num_samples)`
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Vec<Array2<f32>>, SignalError>` - The CQT feature matrix of each channel
+  pub fn process_planar(
+    &self,
+    channels: ArrayView2<f32>,
+    hop_size: usize
+  ) -> Result<Vec<Array2<f32>>, SignalError> {
+    if channels.nrows() == 0 || channels.ncols() == 0 {
+      return Err(SignalError::EmptyInputSignal);
+    }
+
+    channels
+      .axis_iter(Axis(0))
+      .into_par_iter()
+      .map(|channel| {
+        // The rows of a column-major array aren't contiguous
+        match channel.as_slice() {
+          Some(signal) => self.process(signal, hop_size),
+          None => self.process(&channel.to_vec(), hop_size),
+        }
+      })
+      .collect()
+  }
+
+  /// Process the input signal and compute the complex Constant-Q Transform (CQT).
+  ///
+  /// The output is the projection of each frame spectrum through the CQT filterbank,
+  /// with one column per CQT bin. The intermediate `window_length` bins FFT of the
+  /// frames is not exposed. The magnitude of the output is the output of `process`,
+  /// before the single-sided scaling, and its argument is the phase of each bin:
+  ///
+  /// ```
+  /// use cqt_rs::{ create_dummy_audio_signal, CQTParams, Cqt };
+  ///
+  /// let cqt_params = CQTParams::new(20.0, 7902.1, 12, 44100, 4096).unwrap();
+  /// let cqt = Cqt::new(cqt_params);
+  /// let signal = create_dummy_audio_signal(44100, 440.0, 0.5);
+  ///
+  /// let cqt_complex = cqt.process_complex(&signal, 2048).unwrap();
+  /// assert_eq!(cqt_complex.dim(), (10, 108));
+  ///
+  /// let magnitude = cqt_complex.mapv(|x| x.norm());
+  /// let phase = cqt_complex.mapv(|x| x.arg());
+  ///
+  /// assert_eq!(magnitude, cqt.process(&signal, 2048).unwrap());
+  /// assert!(phase.iter().all(|x| x.abs() <= std::f32::consts::PI));
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<Complex<f32>>, SignalError>` - The complex CQT matrix of shape `(num_frames, num_bins)`
+  pub fn process_complex(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<Array2<Complex<f32>>, SignalError> {
+    self.process_complex_with(signal, hop_size, true)
+  }
+
+  /// Compute the complex CQT of the input signal, optionally skipping the Hann window.
+  fn process_complex_with(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    apply_window: bool
+  ) -> Result<Array2<Complex<f32>>, SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    // Assign the input signal to the center of the padded signal, validating the hop size and signal
+    let signal_padded = pad_input_signal(
+      &self.pre_emphasized(signal),
+      window_len,
+      hop_size,
+      self.cqt_params.centering()
+    )?;
+    let num_frames = signal.len() / hop_size;
+
+    // Compute the CQT of each frame of the padded signal
+    self.compute_cqt_frames(num_frames, apply_window, |frame_idx| {
+      let start = frame_idx * hop_size;
+      let end = start + window_len;
+
+      signal_padded.slice(s![start..end])
+    })
+  }
+
+  /// Apply the pre-emphasis filter of the parameters to the input signal, borrowing the
+  /// signal unchanged when the pre-emphasis is disabled.
+  fn pre_emphasized<'a>(&self, signal: &'a [f32]) -> Cow<'a, [f32]> {
+    match self.cqt_params.pre_emphasis() {
+      Some(alpha) => Cow::Owned(pre_emphasize(signal, alpha)),
+      None => Cow::Borrowed(signal),
+    }
+  }
+
+  /// Return the sample `idx` of the input signal after the pre-emphasis filter of the
+  /// parameters, reading the previous sample from the signal so the filter carries across
+  /// frames and regions. Returns `None` outside of the signal.
+  fn pre_emphasized_sample(&self, signal: &[f32], idx: usize) -> Option<f32> {
+    let sample = *signal.get(idx)?;
+
+    Some(match self.cqt_params.pre_emphasis() {
+      Some(alpha) => {
+        let previous_sample = idx.checked_sub(1).map_or(0.0, |previous_idx| signal[previous_idx]);
+        sample - alpha * previous_sample
+      }
+      None => sample,
+    })
+  }
+
+  /// Export the windowed frames of the input signal and the filterbank as contiguous
+  /// real matrices, e.g. to compute the FFT and the projection on a GPU.
+  ///
+  /// The layout is:
+  ///
+  /// * `frames` - A standard (row-major) layout matrix of shape `(num_frames, window_length)`,
+  ///   row `k` holding frame `k` of `process` multiplied by the Hann window, before the FFT.
+  /// * `filterbank_re`, `filterbank_im` - Standard layout matrices of shape
+  ///   `(num_bins, window_length)` holding the real and imaginary parts of the filterbank.
+  ///
+  /// The complex CQT is `FFT(frames) · (filterbank_re + i * filterbank_im)^T`, the FFT being
+  /// the unnormalized forward FFT of each row. `process` returns its magnitude, doubled for the
+  /// bins strictly between DC and Nyquist when the single-sided scaling is enabled. The per-octave
+  /// windows can't be described by a single frame matrix, so they aren't supported.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<ExportedFrames, SignalError>` - The windowed frames and the real and
+  ///   imaginary parts of the filterbank
+  ///
+  /// # Errors
+  ///
+  /// Returns `OctaveWindowsUnsupported` if the per-octave windows are enabled.
+  pub fn export_frames(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<ExportedFrames, SignalError> {
+    if self.octave_filterbanks.is_some() {
+      return Err(SignalError::OctaveWindowsUnsupported);
+    }
+
+    let window_len = self.cqt_params.window_length;
+    let hann_window = &self.cqt_params.hann_window;
+
+    let signal_padded = pad_input_signal(
+      &self.pre_emphasized(signal),
+      window_len,
+      hop_size,
+      self.cqt_params.centering()
+    )?;
+    let num_frames = signal.len() / hop_size;
+
+    let frames = Array2::from_shape_fn((num_frames, window_len), |(frame_idx, n)| {
+      signal_padded[frame_idx * hop_size + n] * hann_window[n]
+    });
+
+    Ok((frames, self.filterbank.mapv(|x| x.re), self.filterbank.mapv(|x| x.im)))
+  }
+
+  /// Process a region of the input signal and compute its Constant-Q Transform (CQT) features.
+  ///
+  /// The frames are laid out as if `process` was called on `signal[start..start + len]`,
+  /// but the samples around the region are used as context instead of the zero padding,
+  /// which is only applied beyond the ends of `signal`. The edge frames of the region are
+  /// thus the frames of the full signal rather than frames of a zero padded slice.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `start` - The first sample of the region
+  /// * `len` - The number of samples of the region
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix of the region
+  pub fn process_region(
+    &self,
+    signal: &[f32],
+    start: usize,
+    len: usize,
+    hop_size: usize
+  ) -> Result<Array2<f32>, SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    if hop_size == 0 || hop_size > window_len {
+      return Err(SignalError::InvalidHopSize);
+    }
+
+    if len == 0 {
+      return Err(SignalError::EmptyInputSignal);
+    }
+
+    if start.checked_add(len).is_none_or(|end| end > signal.len()) {
+      return Err(SignalError::InvalidRegion);
+    }
+
+    // Same layout as `pad_input_signal`, taking the padding from the signal when available
+    let front_padding = self.cqt_params.centering().front_padding(window_len, hop_size);
+    let signal_context = Array1::from_shape_fn(window_len - hop_size + len, |idx| {
+      (start + idx)
+        .checked_sub(front_padding)
+        .and_then(|signal_idx| self.pre_emphasized_sample(signal, signal_idx))
+        .unwrap_or(0.0)
+    });
+    let num_frames = len / hop_size;
+
+    let cqt_filtered = self.compute_cqt_frames(num_frames, true, |frame_idx| {
+      let frame_start = frame_idx * hop_size;
+
+      signal_context.slice(s![frame_start..frame_start + window_len])
+    })?;
+
+    let mut abs_cqt_filtered = magnitudes(&cqt_filtered);
+    self.scale_magnitudes(&mut abs_cqt_filtered);
+
+    Ok(abs_cqt_filtered)
+  }
+
+  /// Compute the Constant-Q Transform (CQT) features of a stream of samples, frame by frame.
+  ///
+  /// The samples are buffered internally and each frame is yielded as soon as its last
+  /// sample is pulled from `iter`. Once `iter` is exhausted, the remaining frames are
+  /// completed with the zero padding of `pad_input_signal`, so the frames are the rows
+  /// of the output of `process` on the collected samples. An empty stream yields no frame.
+  ///
+  /// # Arguments
+  ///
+  /// * `iter` - The samples of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<impl Iterator<Item = Array1<f32>>, SignalError>` - The iterator of the CQT
+  ///   feature vectors of the frames
+  ///
+  /// # Panics
+  ///
+  /// The iterator panics if a frame can't be transformed with the FFT.
+  pub fn frames_from_iter<'a, I: Iterator<Item = f32> + 'a>(
+    &'a self,
+    mut iter: I,
+    hop_size: usize
+  ) -> Result<impl Iterator<Item = Array1<f32>> + 'a, SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    if hop_size == 0 || hop_size > window_len {
+      return Err(SignalError::InvalidHopSize);
+    }
+
+    // The buffer starts with the front padding, frame `k` starting `k * hop_size` samples later
+    let front_padding = self.cqt_params.centering().front_padding(window_len, hop_size);
+    let mut buffer = vec![0.0; front_padding];
+    let pre_emphasis = self.cqt_params.pre_emphasis();
+    let mut previous_sample = 0.0;
+    let mut num_samples = 0;
+    let mut num_frames = 0;
+    let mut exhausted = false;
+
+    Ok(
+      std::iter::from_fn(move || {
+        while !exhausted && buffer.len() < window_len {
+          match iter.next() {
+            Some(sample) => {
+              // The pre-emphasis carries the previous sample across the frames
+              buffer.push(pre_emphasis.map_or(sample, |alpha| sample - alpha * previous_sample));
+              previous_sample = sample;
+              num_samples += 1;
+            }
+            None => {
+              exhausted = true;
+            }
+          }
+        }
+
+        // Like `process`, the signal is split in `num_samples / hop_size` frames
+        if exhausted {
+          if num_frames >= num_samples / hop_size {
+            return None;
+          }
+          buffer.resize(buffer.len().max(window_len), 0.0);
+        }
+
+        let frame = self
+          .process_single_frame(&buffer[..window_len])
+          .expect("Error computing CQT frame");
+        buffer.drain(..hop_size);
+        num_frames += 1;
+
+        Some(frame)
+      })
+    )
+  }
+
+  /// Process the input signal and compute the Constant-Q Transform (CQT) features,
+  /// keeping only the frames whose window lies fully within the input signal.
+  ///
+  /// The leading and trailing frames overlapping the zero padding are dropped, see
+  /// `trimmed_edge_frames` for their count.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix without the edge frames
+  pub fn process_trimmed(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<Array2<f32>, SignalError> {
+    let cqt_features = self.process(signal, hop_size)?;
+    let (leading_frames, trailing_frames) = self.trimmed_edge_frames(signal.len(), hop_size)?;
+
+    let end = cqt_features.nrows() - trailing_frames;
+
+    Ok(cqt_features.slice_move(s![leading_frames..end, ..]))
+  }
+
+  /// Compute the number of leading and trailing frames of `process` whose window
+  /// overlaps the zero padding of the input signal.
+  ///
+  /// # Arguments
+  ///
+  /// * `signal_len` - The number of samples of the input signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(usize, usize), SignalError>` - The number of leading and trailing edge frames
+  pub fn trimmed_edge_frames(
+    &self,
+    signal_len: usize,
+    hop_size: usize
+  ) -> Result<(usize, usize), SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    if hop_size == 0 || hop_size > window_len {
+      return Err(SignalError::InvalidHopSize);
+    }
+
+    if signal_len == 0 {
+      return Err(SignalError::EmptyInputSignal);
+    }
+
+    let num_frames = signal_len / hop_size;
+    // Padding added before the signal, frame `i` starts at `i * hop_size - front_padding`
+    let front_padding = self.cqt_params.centering().front_padding(window_len, hop_size);
+
+    // The first frame starting inside the signal
+    let first_frame = front_padding.div_ceil(hop_size).min(num_frames);
+    // One past the last frame ending inside the signal
+    let end_frame = if signal_len + front_padding >= window_len {
+      ((signal_len + front_padding - window_len) / hop_size + 1).min(num_frames)
+    } else {
+      0
+    };
+
+    Ok((first_frame, num_frames - end_frame.max(first_frame)))
+  }
+
+  /// Process the input signal and compute both the magnitude and the phase of the
+  /// Constant-Q Transform (CQT) in a single pass.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(Array2<f32>, Array2<f32>), SignalError>` - The magnitude and phase matrices
+  pub fn process_polar(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<(Array2<f32>, Array2<f32>), SignalError> {
+    let cqt_filtered = self.process_complex(signal, hop_size)?;
+
+    let mut magnitude = Array2::<f32>::zeros(cqt_filtered.dim());
+    let mut phase = Array2::<f32>::zeros(cqt_filtered.dim());
+
+    // Split each complex value into its magnitude and phase
+    Zip::from(&mut magnitude)
+      .and(&mut phase)
+      .and(&cqt_filtered)
+      .for_each(|magnitude_elem, phase_elem, cqt_elem| {
+        (*magnitude_elem, *phase_elem) = cqt_elem.to_polar();
+      });
+    self.scale_magnitudes(&mut magnitude);
+
+    Ok((magnitude, phase))
+  }
+
+  /// Process the input signal and compute both the magnitude and the power of the
+  /// Constant-Q Transform (CQT) in a single pass.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(Array2<f32>, Array2<f32>), SignalError>` - The magnitude and power matrices
+  pub fn process_mag_and_power(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<(Array2<f32>, Array2<f32>), SignalError> {
+    let cqt_filtered = self.process_complex(signal, hop_size)?;
+
+    let mut magnitude = magnitudes(&cqt_filtered);
+    let mut power = cqt_filtered.mapv(|x| x.norm_sqr());
+    self.scale_magnitudes(&mut magnitude);
+    self.scale_powers(&mut power);
+
+    Ok((magnitude, power))
+  }
+
+  /// Process the input signal and fold its Constant-Q Transform (CQT) into a
+  /// chromagram of 12 pitch classes, without materializing the full CQT matrix.
+  ///
+  /// The frames are framed from the signal, transformed and folded in chunks of a fixed
+  /// number of frames, so besides the chromagram the memory used doesn't grow with the
+  /// signal length. The result matches folding the output of `process` with `chroma`.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The chromagram of shape `(num_frames, 12)`
+  pub fn stream_chroma(&self, signal: &[f32], hop_size: usize) -> Result<Array2<f32>, SignalError> {
+    // An invalid hop size is rejected by `for_each_chunk`
+    let num_frames = signal.len().checked_div(hop_size).unwrap_or(0);
+    let mut chromagram = Array2::<f32>::zeros((num_frames, NUM_PITCH_CLASSES));
+
+    self.for_each_chunk(signal, hop_size, |first_frame, abs_cqt_chunk| {
+      // Fold the frames of the chunk into their chroma rows
+      let chunk_frames = abs_cqt_chunk.nrows();
+      let mut chroma_rows = chromagram.slice_mut(s![first_frame..first_frame + chunk_frames, ..]);
+      for (frame, chroma_row) in abs_cqt_chunk.outer_iter().zip(chroma_rows.outer_iter_mut()) {
+        fold_to_chroma(frame, self.cqt_params.bins_per_octave, chroma_row);
+      }
+    })?;
+
+    Ok(chromagram)
+  }
+
+  /// Process the input signal and call `f` with the Constant-Q Transform (CQT) features of
+  /// each frame, without collecting them into a matrix.
+  ///
+  /// The frames are computed in chunks like in `stream_chroma`, and `f` is called
+  /// sequentially, in frame order, from the calling thread, so it doesn't need to be
+  /// thread-safe. The frame passed to `f` matches the row of the same index of `process`.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  /// * `f` - Called with the index and the CQT feature vector of each frame
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(), SignalError>` - An error if the signal or the hop size is invalid
+  pub fn for_each_frame<F: FnMut(usize, ArrayView1<f32>)>(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    mut f: F
+  ) -> Result<(), SignalError> {
+    self.for_each_chunk(signal, hop_size, |first_frame, abs_cqt_chunk| {
+      for (chunk_idx, frame) in abs_cqt_chunk.outer_iter().enumerate() {
+        f(first_frame + chunk_idx, frame);
+      }
+    })
+  }
+
+  /// Compute the CQT magnitudes of the input signal in chunks of `STREAM_CHUNK_FRAMES` frames,
+  /// calling `f` with the index of the first frame and the magnitudes of each chunk.
+  ///
+  /// The frames are the frames of `process`, framed from the signal chunk by chunk so the
+  /// memory used doesn't grow with the signal length.
+  fn for_each_chunk<F: FnMut(usize, &Array2<f32>)>(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    mut f: F
+  ) -> Result<(), SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    if hop_size == 0 || hop_size > window_len {
+      return Err(SignalError::InvalidHopSize);
+    }
+
+    if signal.is_empty() {
+      return Err(SignalError::EmptyInputSignal);
+    }
+
+    let num_frames = signal.len() / hop_size;
+    // Same padding as `pad_input_signal`, frame `i` starts at `i * hop_size - front_padding`
+    let front_padding = self.cqt_params.centering().front_padding(window_len, hop_size);
+
+    let mut chunk = Array2::<f32>::zeros((STREAM_CHUNK_FRAMES, window_len));
+
+    for first_frame in (0..num_frames).step_by(STREAM_CHUNK_FRAMES) {
+      let chunk_frames = STREAM_CHUNK_FRAMES.min(num_frames - first_frame);
+
+      // Copy the frames of the chunk, reading zeros outside of the signal
+      for (chunk_idx, mut frame) in chunk.axis_iter_mut(Axis(0)).take(chunk_frames).enumerate() {
+        let start = (first_frame + chunk_idx) * hop_size;
+
+        for (n, frame_elem) in frame.iter_mut().enumerate() {
+          *frame_elem = (start + n)
+            .checked_sub(front_padding)
+            .and_then(|sample_idx| self.pre_emphasized_sample(signal, sample_idx))
+            .unwrap_or(0.0);
+        }
+      }
+
+      let cqt_chunk = self.compute_cqt_frames(chunk_frames, true, |chunk_idx| {
+        chunk.row(chunk_idx)
+      })?;
+      let mut abs_cqt_chunk = cqt_chunk.mapv(|x| x.abs());
+      self.scale_magnitudes(&mut abs_cqt_chunk);
+
+      f(first_frame, &abs_cqt_chunk);
+    }
+
+    Ok(())
+  }
+
+  /// Process the input signal and compute the Constant-Q Transform (CQT) in dB
+  /// relative to full scale (dBFS).
+  ///
+  /// Each bin is normalized by its coherent gain, so a unit-amplitude sine at the
+  /// center frequency of a bin reads 0 dBFS in that bin.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  /// * `floor_db` - The lowest value of the output in dBFS, e.g. `-120.0`
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix in dBFS
+  pub fn process_dbfs(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    floor_db: f32
+  ) -> Result<Array2<f32>, SignalError> {
+    let cqt_filtered = self.process_complex(signal, hop_size)?;
+    let coherent_gains = self.coherent_gains();
+
+    let mut dbfs = magnitudes(&cqt_filtered);
+    for (mut column, &gain) in dbfs.axis_iter_mut(Axis(1)).zip(coherent_gains.iter()) {
+      column.mapv_inplace(|x| (20.0 * (x / gain).log10()).max(floor_db));
+    }
+
+    Ok(dbfs)
+  }
+
+  /// Process the input signal and keep the `k` highest magnitude bins of each frame.
+  ///
+  /// This is a sparse representation of the output of `process`, holding `k` values
+  /// per frame instead of `num_bins`.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  /// * `k` - The number of bins to keep per frame, all the bins when greater than `num_bins`
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Vec<Vec<(usize, f32)>>, SignalError>` - The `(bin, magnitude)` pairs of each
+  ///   frame, sorted by descending magnitude
+  pub fn process_topk(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    k: usize
+  ) -> Result<Vec<Vec<(usize, f32)>>, SignalError> {
+    let cqt_magnitudes = self.process(signal, hop_size)?;
+
+    Ok(
+      cqt_magnitudes
+        .outer_iter()
+        .map(|frame| {
+          let mut bins: Vec<(usize, f32)> = frame.iter().copied().enumerate().collect();
+          // Sort by descending magnitude, the lower bin first on ties
+          bins.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+          bins.truncate(k);
+          bins
+        })
+        .collect()
+    )
+  }
+
+  /// Process the input signal and compute the natural logarithm of the Constant-Q
+  /// Transform (CQT) features.
+  ///
+  /// The epsilon is added to the magnitudes before the logarithm, so silent bins map
+  /// to `ln(epsilon)` instead of `-inf`.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  /// * `epsilon` - The positive offset added to the magnitudes, e.g. `1e-6`
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix as `ln(magnitude + epsilon)`
+  pub fn process_log(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    epsilon: f32
+  ) -> Result<Array2<f32>, SignalError> {
+    let mut log_cqt = self.process(signal, hop_size)?;
+    log_cqt.mapv_inplace(|x| (x + epsilon).ln());
+
+    Ok(log_cqt)
+  }
+
+  /// Process the input signal with the hop size closest to a desired time resolution.
+  ///
+  /// The hop size is `target_seconds * sample_rate` rounded to the nearest sample and
+  /// clamped to `[1, window_length]`.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `target_seconds` - The desired time between frames in seconds, e.g. `0.01`
+  ///
+  /// # Returns
+  ///
+  /// * `Result<(Array2<f32>, usize), SignalError>` - The CQT feature matrix and the hop size used
+  pub fn process_time_resolution(
+    &self,
+    signal: &[f32],
+    target_seconds: f32
+  ) -> Result<(Array2<f32>, usize), SignalError> {
+    let hop_size = (target_seconds * (self.cqt_params.sample_rate as f32))
+      .round()
+      .clamp(1.0, self.cqt_params.window_length as f32) as usize;
+
+    Ok((self.process(signal, hop_size)?, hop_size))
+  }
+
+  /// Process the input signal and compute the Constant-Q Transform (CQT) features,
+  /// optionally rejecting a signal shorter than the window.
+  ///
+  /// By default a signal shorter than the window is zero-padded like any other, its frames
+  /// being mostly padding. With `strict_length` it is rejected instead, leaving the caller
+  /// to decide how to handle short clips.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  /// * `strict_length` - Whether to reject a signal shorter than the window length
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  ///
+  /// # Errors
+  ///
+  /// Returns `SignalTooShortForWindow` if `strict_length` is enabled and the signal is
+  /// shorter than the window length, or the errors of `process`.
+  pub fn process_checked(
+    &self,
+    signal: &[f32],
+    hop_size: usize,
+    strict_length: bool
+  ) -> Result<Array2<f32>, SignalError> {
+    let window_length = self.cqt_params.window_length;
+
+    if strict_length && signal.len() < window_length {
+      return Err(SignalError::SignalTooShortForWindow { len: signal.len(), window_length });
+    }
+
+    self.process(signal, hop_size)
+  }
+
+  /// Return the coherent gain of each bin, the magnitude of the bin for a unit-amplitude
+  /// sine at its center frequency, pre-emphasized like the signal, computed on the first
+  /// call and cached.
+  fn coherent_gains(&self) -> &Array1<f32> {
+    self.coherent_gains.get_or_init(|| self.compute_coherent_gains())
+  }
+
+  /// Compute the coherent gain of each bin from its own filter.
+  ///
+  /// Only the output of each bin for the sine at its center frequency is computed: one
+  /// FFT of the windowed sine and its product with the filterbank row of the bin, instead
+  /// of projecting every sine through the whole filterbank.
+  fn compute_coherent_gains(&self) -> Array1<f32> {
+    let window_len = self.cqt_params.window_length;
+    let mut planner = FftPlanner::<f32>::new();
+
+    let Some(octave_filterbanks) = &self.octave_filterbanks else {
+      return self.filter_coherent_gains(
+        &self.cqt_params.hann_window,
+        &self.filterbank,
+        0,
+        0,
+        &mut planner
+      );
+    };
+
+    let mut coherent_gains = Array1::<f32>::zeros(self.filterbank.nrows());
+
+    for octave in octave_filterbanks {
+      // The octave window is centered inside the full window
+      let offset = (window_len - octave.window_length) / 2;
+      let octave_gains = self.filter_coherent_gains(
+        &octave.hann_window,
+        &octave.filterbank,
+        octave.first_bin,
+        offset,
+        &mut planner
+      );
+
+      coherent_gains
+        .slice_mut(s![octave.first_bin..octave.first_bin + octave_gains.len()])
+        .assign(&(octave_gains * octave.gain));
+    }
+
+    coherent_gains
+  }
+
+  /// Compute the coherent gains of the bins of a filterbank starting at `first_bin`, its
+  /// window seeing the full window from `offset` on.
+  fn filter_coherent_gains(
+    &self,
+    hann_window: &[f32],
+    filterbank: &Array2<Complex<f32>>,
+    first_bin: usize,
+    offset: usize,
+    planner: &mut FftPlanner<f32>
+  ) -> Array1<f32> {
+    let sample_rate = self.cqt_params.sample_rate as f32;
+    let remove_dc = self.cqt_params.remove_dc();
+    let pre_emphasis = self.cqt_params.pre_emphasis().unwrap_or(0.0);
+    let fft = planner.plan_fft_forward(hann_window.len());
+
+    let coherent_gains: Vec<f32> = filterbank
+      .axis_iter(Axis(0))
+      .into_par_iter()
+      .enumerate()
+      .map(|(bin_idx, filter)| {
+        let center_freq = self.cqt_params.center_freq(first_bin + bin_idx);
+        let sine_sample = |n: f32| ((2.0 * PI * center_freq * n) / sample_rate).sin();
+        // The pre-emphasized sine over the part of the full window seen by the filter
+        let sine: Vec<f32> = (offset..offset + hann_window.len())
+          .map(|n| sine_sample(n as f32) - pre_emphasis * sine_sample((n as f32) - 1.0))
+          .collect();
+        let dc_offset = if remove_dc { sine.iter().sum::<f32>() / (sine.len() as f32) } else { 0.0 };
+
+        let mut spectrum: Vec<Complex<f32>> = sine
+          .iter()
+          .zip(hann_window)
+          .map(|(&sample, &window_elem)| Complex::new((sample - dc_offset) * window_elem, 0.0))
+          .collect();
+        fft.process(&mut spectrum);
+
+        let output: Complex<f32> = spectrum
+          .iter()
+          .zip(filter.iter())
+          .map(|(&spectrum_elem, &filter_elem)| spectrum_elem * filter_elem)
+          .sum();
+        output.norm()
+      })
+      .collect();
+
+    Array1::from(coherent_gains)
+  }
+
+  /// Compute the Constant-Q Transform (CQT) features of already framed input.
+  ///
+  /// Unlike `process`, no padding or hopping is applied: each row of `frames`
+  /// is transformed as a single frame, row `k` of the output holding row `k` of `frames`.
+  /// The frames are transformed as given, without the pre-emphasis.
+  ///
+  /// # Arguments
+  ///
+  /// * `frames` - An ArrayView2<f32> of shape `(num_frames, window_length)`
+  /// * `apply_window` - Whether to apply the Hann window to each frame
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
+  pub fn process_frames(
+    &self,
+    frames: ArrayView2<f32>,
+    apply_window: bool
+  ) -> Result<Array2<f32>, SignalError> {
+    if frames.nrows() == 0 {
+      return Err(SignalError::EmptyInputSignal);
+    }
+
+    // Every row has the same length, the number of columns
+    if frames.ncols() != self.cqt_params.window_length {
+      return Err(SignalError::FrameLengthMismatch);
+    }
+
+    // Compute the CQT of each row of the frames matrix
+    let cqt_filtered = self.compute_cqt_frames(frames.nrows(), apply_window, |frame_idx| {
+      frames.row(frame_idx)
+    })?;
+
+    let mut abs_cqt_filtered = magnitudes(&cqt_filtered);
+    self.scale_magnitudes(&mut abs_cqt_filtered);
+
+    Ok(abs_cqt_filtered)
+  }
+
+  /// Compute the Constant-Q Transform (CQT) features of a single frame.
+  ///
+  /// The frame is multiplied by the Hann window, transformed with the FFT and
+  /// projected through the CQT filterbank, without any padding or pre-emphasis.
+  ///
+  /// # Arguments
+  ///
+  /// * `frame` - A frame of exactly `window_length` samples
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array1<f32>, SignalError>` - The CQT feature vector of the frame
+  pub fn process_single_frame(&self, frame: &[f32]) -> Result<Array1<f32>, SignalError> {
+    validate_frame(frame, self.cqt_params.window_length)?;
+
+    let frame = ArrayView1::from(frame);
+    let cqt_filtered = self.compute_cqt_frames(1, true, |_| frame)?;
+
+    let mut abs_cqt_filtered = magnitudes(&cqt_filtered);
+    self.scale_magnitudes(&mut abs_cqt_filtered);
+
+    Ok(abs_cqt_filtered.row(0).to_owned())
+  }
+
+  /// Compute the magnitude response of a filterbank bin on a linear frequency grid.
+  ///
+  /// The response is evaluated at `num_points` frequencies evenly spaced over
+  /// `[0, sample_rate / 2]`, and peaks at the center frequency of the bin. With the per-octave
+  /// windows enabled, the response is the one of the octave filter of the bin, scaled by the
+  /// gain of the octave.
+  ///
+  /// # Arguments
+  ///
+  /// * `bin` - The index of the filterbank bin
+  /// * `num_points` - The number of frequencies to evaluate
+  ///
+  /// # Returns
+  ///
+  /// * `Array1<f32>` - The magnitude response of the bin
+  ///
+  /// # Panics
+  ///
+  /// Panics if `bin` is out of the filterbank bounds.
+  pub fn frequency_response(&self, bin: usize, num_points: usize) -> Array1<f32> {
+    let sample_rate = self.cqt_params.sample_rate as f32;
+
+    // The filter of the bin, with the window length and the gain of its octave
+    let (filter, window_len, scale) = match &self.octave_filterbanks {
+      Some(octave_filterbanks) => {
+        let octave = octave_filterbanks
+          .iter()
+          .rev()
+          .find(|octave| octave.first_bin <= bin)
+          .expect("Error finding the octave of the bin");
+        // The octave output is computed on a shorter frame, then scaled by the gain
+        let scale =
+          (octave.gain * (octave.window_length as f32)) / (self.cqt_params.window_length as f32);
+
+        (octave.filterbank.row(bin - octave.first_bin), octave.window_length, scale)
+      }
+      None => (self.filterbank.row(bin), self.cqt_params.window_length, 1.0),
+    };
+
+    // Recover the complex window of the bin from its spectrum
+    let mut complex_window = filter.to_owned();
+    FftPlanner::<f32>::new()
+      .plan_fft_inverse(window_len)
+      .process(complex_window.as_slice_mut().expect("Error applying ifft to filter"));
+
+    let freq_step = sample_rate / 2.0 / (num_points.max(2) - 1) as f32;
+    // Evaluate the response at the frequencies the complex window turns at
+    let sign = -self.cqt_params.phase_convention().sign();
+
+    Array1::from_shape_fn(num_points, |point| {
+      let freq = (point as f32) * freq_step;
+
+      // Evaluate the DTFT of the complex window at the frequency
+      let response: Complex<f32> = complex_window
+        .iter()
+        .enumerate()
+        .map(|(n, &window_elem)| {
+          window_elem * Complex::new(0.0, (sign * 2.0 * PI * freq * (n as f32)) / sample_rate).exp()
+        })
+        .sum();
+
+      (scale * response.abs()) / (window_len as f32)
+    })
+  }
+
+  /// Write the magnitude response of every filterbank bin to a CSV file.
+  ///
+  /// The first line is a header holding `bin` followed by the `num_points` frequencies in Hz,
+  /// evenly spaced over `[0, sample_rate / 2]` as in `frequency_response`. Each following line
+  /// holds the index of a bin followed by its magnitude response, one line per bin.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - The path of the CSV file to write
+  /// * `num_points` - The number of frequencies to evaluate
+  ///
+  /// # Errors
+  ///
+  /// Returns an `io::Error` if the file can't be created or written.
+  pub fn write_response_csv<P: AsRef<Path>>(&self, path: P, num_points: usize) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let freq_step = (self.cqt_params.sample_rate as f32) / 2.0 / (num_points.max(2) - 1) as f32;
+
+    write!(writer, "bin")?;
+    for point in 0..num_points {
+      write!(writer, ",{}", (point as f32) * freq_step)?;
+    }
+    writeln!(writer)?;
+
+    for bin in 0..self.filterbank.nrows() {
+      write!(writer, "{}", bin)?;
+      for magnitude in self.frequency_response(bin, num_points) {
+        write!(writer, ",{}", magnitude)?;
+      }
+      writeln!(writer)?;
+    }
+
+    writer.flush()
+  }
+
+  /// Estimate the condition number of the filterbank.
+  ///
+  /// The estimate is a cheap proxy of the condition number of `F·F^H`: the ratio of the
+  /// largest to the smallest summed filter energy `Σ_k |F[k, f]|²` over the FFT frequencies
+  /// `f` between the center frequencies of the first and last bins. Gaps between the bins or
+  /// too much overlap make the summed energy uneven and the estimate large, a sign that
+  /// inverting the transform is unstable. A perfectly even coverage gives 1.
+  ///
+  /// # Returns
+  ///
+  /// * `f32` - The condition number estimate, infinite when a frequency isn't covered at all
+  ///   or when no FFT frequency lies between the first and last center frequencies
+  pub fn filterbank_condition(&self) -> f32 {
+    let window_len = self.cqt_params.window_length;
+    let sample_rate = self.cqt_params.sample_rate as f32;
+    let last_bin = self.filterbank.nrows() - 1;
+    let fft_bin = |freq: f32| (freq * (window_len as f32)) / sample_rate;
+
+    // FFT frequencies between the center frequencies of the first and last bins
+    let first_freq = (fft_bin(self.cqt_params.center_freq(0)).ceil() as usize).max(1);
+    let last_freq = (fft_bin(self.cqt_params.center_freq(last_bin)) as usize).min(window_len - 1);
+
+    if last_freq < first_freq {
+      return f32::INFINITY;
+    }
+
+    // With the negative convention, the complex windows turn at the negative frequencies
+    let covered_columns = match self.cqt_params.phase_convention() {
+      PhaseConvention::Negative => s![.., window_len - last_freq..=window_len - first_freq],
+      PhaseConvention::Positive => s![.., first_freq..=last_freq],
+    };
+    let (min_energy, max_energy) = self.filterbank
+      .slice(covered_columns)
+      .axis_iter(Axis(1))
+      .map(|column| column.iter().map(|x| x.norm_sqr()).sum::<f32>())
+      .fold((f32::MAX, 0.0f32), |(min, max), energy| (min.min(energy), max.max(energy)));
+
+    max_energy / min_energy
+  }
+
+  /// Compute the analysis latency in samples for a hop size.
+  ///
+  /// `pad_input_signal` pads the signal with `front_padding` zeros at the start, so the
+  /// center of frame `k` falls on the input sample `k * hop_size + latency`. With centered
+  /// frames, the latency is `window_length / 2 - (window_length - hop_size) / 2` and an event
+  /// at the input is centered in a frame `latency` samples after the frame start. With causal
+  /// frames, the latency is `hop_size - window_length / 2`, negative when the frames overlap
+  /// by more than half a window: the frames lag behind the input.
+  ///
+  /// # Arguments
+  ///
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `isize` - The latency in samples
+  pub fn latency_samples(&self, hop_size: usize) -> isize {
+    let window_len = self.cqt_params.window_length;
+    let front_padding = self.cqt_params.centering().front_padding(window_len, hop_size);
+
+    ((window_len / 2) as isize) - (front_padding as isize)
+  }
+
+  /// Compute the overlap-add gain of the analysis and synthesis windows for a hop size.
+  ///
+  /// With the frames windowed by the analysis window and overlap-added with the synthesis
+  /// window, the signal is scaled per sample by the sum of the products of both windows
+  /// overlapping it. The returned `window_length` samples hold that sum for one window in
+  /// steady state, so an inverse transform can divide it out. The gain is periodic with a
+  /// period of `hop_size`.
+  ///
+  /// By default the Hann window is used both for analysis and synthesis, and the squared
+  /// Hann window satisfies the COLA condition (constant gain) for `hop_size = window_length / 4`
+  /// and smaller power-of-two divisions of the window. At `hop_size = window_length / 2` the
+  /// gain ripples between 0.5 and 1 times its maximum, unless a synthesis window such as a
+  /// rectangular one is set with `CQTParams::with_synth_window`.
+  ///
+  /// # Arguments
+  ///
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array1<f32>, SignalError>` - The per-sample overlap-add gain
+  pub fn cola_gain(&self, hop_size: usize) -> Result<Array1<f32>, SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    if hop_size == 0 || hop_size > window_len {
+      return Err(SignalError::InvalidHopSize);
+    }
+
+    // Sum the window products landing on the same position within a hop
+    let mut hop_gain = Array1::<f32>::zeros(hop_size);
+    let windows = self.cqt_params.hann_window.iter().zip(self.cqt_params.synth_window());
+    for (n, (window_elem, synth_window_elem)) in windows.enumerate() {
+      hop_gain[n % hop_size] += window_elem * synth_window_elem;
+    }
+
+    Ok(Array1::from_shape_fn(window_len, |n| hop_gain[n % hop_size]))
+  }
+
+  /// Apply the single-sided scaling to a magnitude matrix, when enabled.
+  ///
+  /// The bins with a center frequency strictly between DC and Nyquist are doubled.
+  fn scale_magnitudes(&self, magnitude: &mut Array2<f32>) {
+    self.scale_single_sided(magnitude, 2.0);
+  }
+
+  /// Apply the single-sided scaling to a power matrix, when enabled.
+  ///
+  /// The bins with a center frequency strictly between DC and Nyquist are quadrupled.
+  fn scale_powers(&self, power: &mut Array2<f32>) {
+    self.scale_single_sided(power, 4.0);
+  }
+
+  /// Multiply the bins with a center frequency strictly between DC and Nyquist by
+  /// `gain`, when the single-sided scaling is enabled.
+  fn scale_single_sided(&self, matrix: &mut Array2<f32>, gain: f32) {
+    if !self.cqt_params.single_sided() {
+      return;
+    }
+
+    let nyquist = self.cqt_params.nyquist();
+
+    for (bin, mut column) in matrix.axis_iter_mut(Axis(1)).enumerate() {
+      let center_freq = self.cqt_params.center_freq(bin);
+
+      if center_freq > 0.0 && center_freq < nyquist {
+        column *= gain;
+      }
+    }
+  }
+
+  /// Compute the complex CQT of `num_frames` frames of `window_length` samples.
+  ///
+  /// Each frame is optionally multiplied by the Hann window, transformed with the FFT
+  /// and projected through the CQT filterbank. With the per-octave windows enabled,
+  /// each octave is computed on the center of the frames with its own window length.
+  ///
+  /// # Arguments
+  ///
+  /// * `num_frames` - The number of frames to process
+  /// * `apply_window` - Whether to apply the Hann window to each frame
+  /// * `frame_at` - Returns the frame for a given frame index
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<Complex<f32>>, SignalError>` - The complex CQT matrix of shape `(num_frames, num_bins)`
+  fn compute_cqt_frames<'a, T: FrameSample + 'a, F>(
+    &self,
+    num_frames: usize,
+    apply_window: bool,
+    frame_at: F
+  ) -> Result<Array2<Complex<f32>>, SignalError>
+    where F: Fn(usize) -> ArrayView1<'a, T> + Sync
+  {
+    let Some(octave_filterbanks) = &self.octave_filterbanks else {
+      return transform_frames(
+        num_frames,
+        &self.cqt_params.hann_window,
+        &self.filterbank,
+        apply_window,
+        self.cqt_params.remove_dc(),
+        frame_at
+      );
+    };
+
+    let window_len = self.cqt_params.window_length;
+    let mut cqt_output = Array2::<Complex<f32>>::zeros((num_frames, self.filterbank.nrows()));
+
+    for octave in octave_filterbanks {
+      // Center the octave window inside the full window so the frames stay aligned
+      let offset = (window_len - octave.window_length) / 2;
+      let end = offset + octave.window_length;
+      let last_bin = octave.first_bin + octave.filterbank.nrows();
+
+      let octave_output = transform_frames(
+        num_frames,
+        &octave.hann_window,
+        &octave.filterbank,
+        apply_window,
+        self.cqt_params.remove_dc(),
+        |frame_idx| frame_at(frame_idx).slice_move(s![offset..end])
+      )?;
+
+      cqt_output
+        .slice_mut(s![.., octave.first_bin..last_bin])
+        .assign(&octave_output.mapv(|x| x * octave.gain));
+    }
+
+    Ok(cqt_output)
+  }
+}
+
+/// Transform `num_frames` frames with the FFT and project them through a filterbank.
+///
+/// The frames are transformed in parallel, each task writing only the output row of its
+/// frame index, so row `k` of the output always holds the frame returned by `frame_at(k)`.
+///
+/// # Arguments
+///
+/// * `num_frames` - The number of frames to process
+/// * `hann_window` - The Hann window, its length is the length of the frames
+/// * `filterbank` - The filterbank of shape `(num_bins, window_length)`
+/// * `apply_window` - Whether to apply the Hann window to each frame
+/// * `remove_dc` - Whether to subtract the mean of each frame before windowing
+/// * `frame_at` - Returns the frame for a given frame index
+///
+/// # Returns
+///
+/// * `Result<Array2<Complex<f32>>, SignalError>` - The complex CQT matrix of shape `(num_frames, num_bins)`
+fn transform_frames<'a, T: FrameSample + 'a, F>(
+  num_frames: usize,
+  hann_window: &[f32],
+  filterbank: &Array2<Complex<f32>>,
+  apply_window: bool,
+  remove_dc: bool,
+  frame_at: F
+) -> Result<Array2<Complex<f32>>, SignalError>
+  where F: Fn(usize) -> ArrayView1<'a, T> + Sync
+{
+  let fft_frames = compute_fft_frames(num_frames, hann_window, apply_window, remove_dc, frame_at)?;
+
+  // Apply the CQT filterbank to the FFT output matrix
+  Ok(project_frames(&fft_frames, filterbank))
+}
+
+/// Transform `num_frames` frames with the FFT, in parallel.
+///
+/// # Arguments
+///
+/// * `num_frames` - The number of frames to process
+/// * `hann_window` - The Hann window, its length is the length of the frames
+/// * `apply_window` - Whether to apply the Hann window to each frame
+/// * `remove_dc` - Whether to subtract the mean of each frame before windowing
+/// * `frame_at` - Returns the frame for a given frame index
+///
+/// # Returns
+///
+/// * `Result<Array2<Complex<f32>>, SignalError>` - The FFT of the frames of shape `(num_frames, window_length)`
+fn compute_fft_frames<'a, T: FrameSample + 'a, F>(
+  num_frames: usize,
+  hann_window: &[f32],
+  apply_window: bool,
+  remove_dc: bool,
+  frame_at: F
+) -> Result<Array2<Complex<f32>>, SignalError>
+  where F: Fn(usize) -> ArrayView1<'a, T> + Sync
+{
+  let window_len = hann_window.len();
+
+  // Initialize the matrix to store the FFT output for each frame
+  let mut cqt_output = Array2::<Complex<f32>>::zeros((num_frames, window_len));
+  let fft = FftPlanner::<f32>::new().plan_fft_forward(window_len);
+
+  // Compute the CQT for each frame
+  cqt_output
+    .axis_iter_mut(Axis(0))
+    .into_par_iter()
+    .enumerate()
+    .try_for_each(|(frame_idx, mut fft_output_row)| {
+      // Get the frame
+      let frame = frame_at(frame_idx);
+      // Get the DC offset to remove from the frame
+      let dc_offset = if remove_dc {
+        frame.mapv(T::to_complex).mean().unwrap_or_default()
+      } else {
+        Complex::new(0.0, 0.0)
+      };
+
+      if apply_window {
+        // Perform element-wise multiplication of the frame with the Hann window,
+        // and store the result in the fft_output_row
+        Zip::from(&mut fft_output_row)
+          .and(frame)
+          .and(hann_window)
+          .par_for_each(|row_elem, &frame_elem, &window_elem| {
+            *row_elem = (frame_elem.to_complex() - dc_offset) * window_elem;
+          });
+      } else {
+        Zip::from(&mut fft_output_row)
+          .and(frame)
+          .par_for_each(|row_elem, &frame_elem| {
+            *row_elem = frame_elem.to_complex() - dc_offset;
+          });
+      }
+
+      // Perform FFT
+      fft.process(fft_output_row.as_slice_mut().ok_or(SignalError::FFTError)?);
+
+      Ok(())
+    })?;
+
+  Ok(cqt_output)
+}
+
+/// Compute the analytic signal of a real signal with the FFT-based Hilbert transform.
+///
+/// The spectrum of the whole signal is kept at DC and Nyquist, doubled at the positive
+/// frequencies and zeroed at the negative ones before the inverse FFT, so the real part
+/// is the signal itself and the imaginary part its Hilbert transform.
+///
+/// # Arguments
+///
+/// * `signal` - The real input signal
+///
+/// # Returns
+///
+/// * `(Vec<f32>, Vec<f32>)` - The real and imaginary parts of the analytic signal
+fn analytic_signal(signal: &[f32]) -> (Vec<f32>, Vec<f32>) {
+  let signal_len = signal.len();
+  let mut spectrum: Vec<Complex<f32>> = signal
+    .iter()
+    .map(|&sample| Complex::new(sample, 0.0))
+    .collect();
+
+  let mut planner = FftPlanner::<f32>::new();
+  planner.plan_fft_forward(signal_len).process(&mut spectrum);
+
+  // The positive frequencies are the bins strictly between DC and Nyquist
+  let nyquist_bin = signal_len.div_ceil(2);
+  for (bin, value) in spectrum.iter_mut().enumerate().skip(1) {
+    if bin < nyquist_bin {
+      *value *= 2.0;
+    } else if bin > signal_len / 2 {
+      *value = Complex::new(0.0, 0.0);
+    }
+  }
+
+  planner.plan_fft_inverse(signal_len).process(&mut spectrum);
+
+  // The inverse FFT isn't normalized
+  let scale = 1.0 / (signal_len as f32);
+  spectrum
+    .iter()
+    .map(|value| (value.re * scale, value.im * scale))
+    .unzip()
+}
+
+/// Compute the element-wise magnitudes of a complex CQT matrix.
+///
+/// The magnitudes are computed in parallel for the matrices of at least
+/// `PARALLEL_MAGNITUDE_THRESHOLD` elements and sequentially otherwise.
+fn magnitudes(cqt_output: &Array2<Complex<f32>>) -> Array2<f32> {
+  compute_magnitudes(cqt_output, cqt_output.len() >= PARALLEL_MAGNITUDE_THRESHOLD)
+}
+
+/// Compute the element-wise magnitudes of a complex CQT matrix, sequentially or in parallel.
+fn compute_magnitudes(cqt_output: &Array2<Complex<f32>>, parallel: bool) -> Array2<f32> {
+  if parallel {
+    Zip::from(cqt_output).par_map_collect(|x| x.abs())
+  } else {
+    cqt_output.mapv(|x| x.abs())
+  }
+}
+
+/// Project the FFT of the frames through a filterbank.
+///
+/// The product goes through `general_mat_mul` and the pure-Rust `matrixmultiply`. Filterbanks
+/// of at least `PARALLEL_PROJECTION_BINS` bins are projected in parallel tiles of bins.
+///
+/// # Arguments
+///
+/// * `fft_frames` - The FFT of the frames of shape `(num_frames, window_length)`
+/// * `filterbank` - The filterbank of shape `(num_bins, window_length)`
+///
+/// # Returns
+///
+/// * `Array2<Complex<f32>>` - The complex CQT matrix of shape `(num_frames, num_bins)`
+fn project_frames(
+  fft_frames: &Array2<Complex<f32>>,
+  filterbank: &Array2<Complex<f32>>
+) -> Array2<Complex<f32>> {
+  project_frames_with(fft_frames, filterbank, filterbank.nrows() >= PARALLEL_PROJECTION_BINS)
+}
+
+/// Project the FFT of the frames through a filterbank, in one product or in parallel tiles.
+///
+/// Each tile multiplies the frames by `PROJECTION_TILE_BINS` filters and writes only the
+/// output columns of its bins.
+fn project_frames_with(
+  fft_frames: &Array2<Complex<f32>>,
+  filterbank: &Array2<Complex<f32>>,
+  parallel: bool
+) -> Array2<Complex<f32>> {
+  let mut cqt_output = Array2::<Complex<f32>>::zeros((fft_frames.nrows(), filterbank.nrows()));
+
+  if !parallel {
+    general_mat_mul(
+      Complex::new(1.0, 0.0),
+      fft_frames,
+      &filterbank.t(),
+      Complex::new(0.0, 0.0),
+      &mut cqt_output
+    );
+
+    return cqt_output;
+  }
+
+  cqt_output
+    .axis_chunks_iter_mut(Axis(1), PROJECTION_TILE_BINS)
+    .into_par_iter()
+    .zip(filterbank.axis_chunks_iter(Axis(0), PROJECTION_TILE_BINS).into_par_iter())
+    .for_each(|(mut output_tile, filter_tile)| {
+      general_mat_mul(
+        Complex::new(1.0, 0.0),
+        fft_frames,
+        &filter_tile.t(),
+        Complex::new(0.0, 0.0),
+        &mut output_tile
+      );
+    });
+
+  cqt_output
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{ env, fs };
+
+  use approx::{ assert_abs_diff_eq, assert_relative_eq };
+  use ndarray::ShapeBuilder;
+
+  use crate::{ chroma, create_dummy_audio_signal, Centering, FilterNorm };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_new_cqt() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    assert_eq!(cqt.filterbank().dim(), (108, 4096));
+  }
+
+  #[test]
+  fn test_cqt_is_send_sync() {
+    fn _assert_send_sync<T: Send + Sync>() {}
+    _assert_send_sync::<Cqt>();
+  }
+
+  #[test]
+  fn test_try_new_cqt() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::try_new(cqt_params);
+
+    assert!(cqt.is_ok());
+    assert_eq!(cqt.unwrap().filterbank.dim(), (108, 4096));
+  }
+
+  #[test]
+  fn test_phase_convention() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let positive_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_phase_convention(PhaseConvention::Positive);
+    let cqt = Cqt::new(cqt_params);
+    let positive_cqt = Cqt::new(positive_cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    // The positive convention conjugates the complex output, up to the rounding errors
+    // relative to the peak magnitude
+    let result = cqt.process_complex(&signal, 2048).unwrap();
+    let positive_result = positive_cqt.process_complex(&signal, 2048).unwrap();
+    let tolerance = 1e-5 * result.fold(0.0f32, |max, value| max.max(value.norm()));
+    Zip::from(&result)
+      .and(&positive_result)
+      .for_each(|value, positive_value| {
+        assert_abs_diff_eq!(positive_value.re, value.re, epsilon = tolerance);
+        assert_abs_diff_eq!(positive_value.im, -value.im, epsilon = tolerance);
+      });
+
+    // The magnitudes and the filterbank diagnostics are identical
+    let magnitudes = cqt.process(&signal, 2048).unwrap();
+    let positive_magnitudes = positive_cqt.process(&signal, 2048).unwrap();
+    Zip::from(&magnitudes)
+      .and(&positive_magnitudes)
+      .for_each(|&value, &positive_value| {
+        assert_abs_diff_eq!(positive_value, value, epsilon = tolerance);
+      });
+    assert_relative_eq!(
+      positive_cqt.filterbank_condition(),
+      cqt.filterbank_condition(),
+      max_relative = 1e-3
+    );
+
+    let response = cqt.frequency_response(60, 512);
+    let positive_response = positive_cqt.frequency_response(60, 512);
+    Zip::from(&response)
+      .and(&positive_response)
+      .for_each(|&value, &positive_value| {
+        assert_abs_diff_eq!(positive_value, value, epsilon = 1e-3);
+      });
+  }
+
+  #[test]
+  fn test_build_many() {
+    let cqt_params = |bins_per_octave: usize| {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, bins_per_octave, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+
+    let cqts = Cqt::build_many(vec![cqt_params(12), cqt_params(24), cqt_params(36)]);
+    assert_eq!(cqts.len(), 3);
+
+    for (cqt, bins_per_octave) in cqts.iter().zip([12, 24, 36]) {
+      assert_eq!(cqt.cqt_params(), &cqt_params(bins_per_octave));
+      assert_eq!(cqt.filterbank, Cqt::new(cqt_params(bins_per_octave)).filterbank);
+    }
+  }
+
+  #[test]
+  fn test_cqt_with_planner() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let planner_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let mut planner = FftPlanner::new();
+    let cqt = Cqt::new(cqt_params);
+    let planner_cqt = Cqt::with_planner(planner_cqt_params, &mut planner).unwrap();
+
+    assert_eq!(planner_cqt.filterbank, cqt.filterbank);
+  }
+
+  #[test]
+  fn test_retune() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let tuned_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_tuning_cents(-25.0);
+    let mut cqt = Cqt::new(cqt_params);
+    let tuned_cqt = Cqt::new(tuned_cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    // Cache the coherent gains and the reassignment filterbanks of the previous tuning
+    cqt.process_dbfs(&signal, 2048, -120.0).unwrap();
+    cqt.process_reassigned(&signal, 2048).unwrap();
+    cqt.retune(-25.0).unwrap();
+
+    assert_eq!(cqt.cqt_params().tuning_cents(), -25.0);
+    assert_eq!(cqt.filterbank, tuned_cqt.filterbank);
+    assert_eq!(cqt.process(&signal, 2048).unwrap(), tuned_cqt.process(&signal, 2048).unwrap());
+    assert_eq!(
+      cqt.process_dbfs(&signal, 2048, -120.0).unwrap(),
+      tuned_cqt.process_dbfs(&signal, 2048, -120.0).unwrap()
+    );
+    assert_eq!(
+      cqt.process_reassigned(&signal, 2048).unwrap(),
+      tuned_cqt.process_reassigned(&signal, 2048).unwrap()
+    );
+  }
+
+  #[test]
+  fn test_retune_clamped_max_freq() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_clamped_max_freq(true);
+    let mut cqt = Cqt::new(cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    // A semitone up pushes the last clamped bin above max_freq
+    cqt.retune(100.0).unwrap();
+    let num_bins = cqt.cqt_params().num_bins();
+
+    assert_eq!(num_bins, 103);
+    assert!(cqt.cqt_params().center_freq(num_bins - 1) <= MAX_FREQ);
+    assert_eq!(cqt.filterbank.dim(), (num_bins, WINDOW_LENGTH));
+    assert_eq!(cqt.process(&signal, 2048).unwrap().ncols(), num_bins);
+  }
+
+  #[test]
+  fn test_process_valid_signal() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let signal = vec![0.0; 1024];
+    let hop_size = 512;
+    let result = cqt.process(&signal, hop_size);
+    assert!(result.is_ok());
+    let cqt_features = result.unwrap();
+    assert_eq!(cqt_features.dim(), (2, 108));
+  }
+
+  #[test]
+  fn test_process_sine_wave() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let freq = 440.0;
+    let hop_size = 512;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, freq, 1.0);
+    let result = cqt.process(&signal, hop_size).unwrap();
+
+    let bin_index = ((freq / MIN_FREQ).log2() * (BINS_PER_OCTAVE as f32)).round() as usize;
+    let max_value = result.column(bin_index).iter().cloned().fold(f32::MIN, f32::max);
+
+    assert_relative_eq!(max_value, 19386750.0, max_relative = 1e-6);
+  }
+
+  #[test]
+  fn test_process_i16() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+    let bin_index = 60;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| 0.5 * (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+    let pcm_signal: Vec<i16> = signal
+      .iter()
+      .map(|&sample| (sample * 32768.0).round() as i16)
+      .collect();
+
+    let result = cqt.process(&signal, hop_size).unwrap();
+    let pcm_result = cqt.process_i16(&pcm_signal, hop_size).unwrap();
+    let peak_bin = |row: ArrayView1<f32>| {
+      row
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > row[peak] { idx } else { peak })
+    };
+
+    let frame_idx = result.nrows() / 2;
+    assert_eq!(pcm_result.dim(), result.dim());
+    assert_eq!(peak_bin(pcm_result.row(frame_idx)), bin_index);
+    assert_eq!(peak_bin(result.row(frame_idx)), bin_index);
+    assert_relative_eq!(
+      pcm_result[[frame_idx, bin_index]],
+      result[[frame_idx, bin_index]],
+      max_relative = 1e-3
+    );
+  }
+
+  #[test]
+  fn test_process_empty_signal() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let signal = vec![];
+    let hop_size = 512;
+    let result = cqt.process(&signal, hop_size);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), SignalError::EmptyInputSignal);
+  }
+
+  #[test]
+  fn test_process_invalid_hop_size() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let signal = vec![0.0; 128];
+    let hop_size = 0;
+    let result = cqt.process(&signal, hop_size);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), SignalError::InvalidHopSize);
+  }
+
+  #[test]
+  fn test_process_hop_size_greater_than_window_length() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // The padding rejects the hop size instead of panicking
+    let signal = vec![0.0; 8192];
+    let hop_size = WINDOW_LENGTH + 1;
+    let result = cqt.process(&signal, hop_size);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), SignalError::InvalidHopSize);
+  }
+
+  #[test]
+  fn test_process_signal_of_one_window() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // A tone at the center of a bin lasting exactly one window
+    let bin_index = 60;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let signal: Vec<f32> = (0..WINDOW_LENGTH)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    // Without padding, the single frame is the whole signal
+    let result = cqt.process(&signal, WINDOW_LENGTH).unwrap();
+    assert_eq!(result.dim(), (1, cqt.cqt_params.num_bins()));
+    assert_eq!(result.row(0), cqt.process_single_frame(&signal).unwrap());
+
+    let frame = result.row(0);
+    let peak = frame
+      .iter()
+      .enumerate()
+      .fold(0, |peak, (idx, &value)| if value > frame[peak] { idx } else { peak });
+    assert_eq!(peak, bin_index);
+  }
+
+  #[test]
+  fn test_process_frames_matches_process() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    // Frame the padded signal manually
+    let num_frames = signal.len() / hop_size;
+    let signal_padded = pad_input_signal(
+      &signal,
+      WINDOW_LENGTH,
+      hop_size,
+      Centering::Centered
+    ).unwrap();
+    let frames = Array2::from_shape_fn((num_frames, WINDOW_LENGTH), |(frame_idx, sample_idx)| {
+      signal_padded[frame_idx * hop_size + sample_idx]
+    });
+
+    let expected = cqt.process(&signal, hop_size).unwrap();
+    let result = cqt.process_frames(frames.view(), true).unwrap();
+
+    assert_eq!(result, expected);
+  }
+
+  #[test]
+  fn test_process_with_rectangular_window() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+
+    // A sine completing exactly `cycles` periods per window, so the DFT of any frame
+    // has a single nonzero bin pair
+    let cycles = 41;
+    let signal: Vec<f32> = (0..16 * hop_size)
+      .map(|n| (2.0 * PI * (cycles as f32) * (n as f32) / (WINDOW_LENGTH as f32)).sin())
+      .collect();
+
+    let windowed = cqt.process_with(&signal, hop_size, true).unwrap();
+    let rectangular = cqt.process_with(&signal, hop_size, false).unwrap();
+    assert_eq!(windowed, cqt.process(&signal, hop_size).unwrap());
+
+    // Analytic DFT of the frame starting at `frame_start` projected through the filterbank
+    let frame_idx = 8;
+    let frame_start = frame_idx * hop_size - (WINDOW_LENGTH - hop_size) / 2;
+    let phase = (2.0 * PI * (cycles as f32) * (frame_start as f32)) / (WINDOW_LENGTH as f32);
+    let amplitude = (WINDOW_LENGTH as f32) / 2.0;
+    let positive = Complex::new(0.0, phase).exp() * Complex::new(0.0, -amplitude);
+    let negative = positive.conj();
+    let mut expected = Array2::from_shape_fn((1, cqt.filterbank.nrows()), |(_, bin)| {
+      (positive * cqt.filterbank[[bin, cycles]] +
+        negative * cqt.filterbank[[bin, WINDOW_LENGTH - cycles]]).abs()
+    });
+    cqt.scale_magnitudes(&mut expected);
+
+    let max_error = |matrix: &Array2<f32>| {
+      Zip::from(matrix.row(frame_idx))
+        .and(expected.row(0))
+        .fold(0.0_f32, |max, &value, &expected| max.max((value - expected).abs()))
+    };
+    let peak = expected.fold(0.0_f32, |max, &value| max.max(value));
+
+    assert!(max_error(&rectangular) < 1e-3 * peak);
+    assert!(max_error(&rectangular) < max_error(&windowed));
+  }
+
+  #[test]
+  fn test_process_frame_order() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let num_frames = 64;
+
+    // Frame `k` holds a centered impulse of amplitude `k + 1`
+    let frames = Array2::from_shape_fn((num_frames, WINDOW_LENGTH), |(frame_idx, sample_idx)| {
+      if sample_idx == WINDOW_LENGTH / 2 { (frame_idx + 1) as f32 } else { 0.0 }
+    });
+
+    let result = cqt.process_frames(frames.view(), true).unwrap();
+    let unit_energy = result.row(0).sum();
+    for (frame_idx, row) in result.outer_iter().enumerate() {
+      assert_relative_eq!(row.sum(), unit_energy * ((frame_idx + 1) as f32), max_relative = 1e-4);
+    }
+    assert_eq!(cqt.process_frames(frames.view(), true).unwrap(), result);
+
+    // An impulse in the signal lands in the row of the frame centered on it
+    let hop_size = 512;
+    let front_padding = (WINDOW_LENGTH - hop_size) / 2;
+    for impulse_frame in [3, 17, 40] {
+      let mut signal = vec![0.0; num_frames * hop_size];
+      signal[impulse_frame * hop_size + WINDOW_LENGTH / 2 - front_padding] = 1.0;
+
+      let result = cqt.process(&signal, hop_size).unwrap();
+      let frame_energy = result.sum_axis(Axis(1));
+      let peak_frame = frame_energy
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > frame_energy[peak] { idx } else { peak });
+
+      assert_eq!(peak_frame, impulse_frame);
+    }
+  }
+
+  #[test]
+  fn test_process_frames_invalid_frame_length() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let frames = Array2::<f32>::zeros((2, WINDOW_LENGTH / 2));
+    let result = cqt.process_frames(frames.view(), false);
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err(), SignalError::FrameLengthMismatch);
+
+    // A non standard layout view is checked without copying its rows
+    let frames = Array2::<f32>::zeros((WINDOW_LENGTH / 2, 2));
+    let result = cqt.process_frames(frames.t(), false);
+    assert_eq!(result.unwrap_err(), SignalError::FrameLengthMismatch);
+
+    let frames = Array2::<f32>::zeros((0, WINDOW_LENGTH));
+    let result = cqt.process_frames(frames.view(), false);
+    assert_eq!(result.unwrap_err(), SignalError::EmptyInputSignal);
+  }
+
+  #[test]
+  fn test_process_single_frame() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let freq = 440.0;
+    let frame: Vec<f32> = (0..WINDOW_LENGTH)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let result = cqt.process_single_frame(&frame).unwrap();
+    let peak_bin = result
+      .iter()
+      .enumerate()
+      .fold(0, |peak, (idx, &value)| if value > result[peak] { idx } else { peak });
+
+    let bin_index = ((freq / MIN_FREQ).log2() * (BINS_PER_OCTAVE as f32)).round() as usize;
+    assert_eq!(result.len(), 108);
+    assert_eq!(peak_bin, bin_index);
+  }
+
+  #[test]
+  fn test_process_single_frame_invalid_frame_length() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let result = cqt.process_single_frame(&[0.0; WINDOW_LENGTH - 1]);
+    assert_eq!(result, Err(SignalError::FrameLengthMismatch));
+  }
+
+  #[test]
+  fn test_process_trimmed() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 1.0);
+
+    for (hop_size, expected_edge_frames) in [(2048, (1, 0)), (512, (4, 4))] {
+      let num_frames = signal.len() / hop_size;
+      let edge_frames = cqt.trimmed_edge_frames(signal.len(), hop_size).unwrap();
+      assert_eq!(edge_frames, expected_edge_frames);
+
+      let (leading_frames, trailing_frames) = edge_frames;
+      let result = cqt.process(&signal, hop_size).unwrap();
+      let trimmed = cqt.process_trimmed(&signal, hop_size).unwrap();
+
+      assert_eq!(trimmed.nrows(), num_frames - leading_frames - trailing_frames);
+      assert_eq!(trimmed, result.slice(s![leading_frames..num_frames - trailing_frames, ..]));
+    }
+  }
+
+  #[test]
+  fn test_process_trimmed_short_signal() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // No window fits in a signal shorter than the window
+    let signal = vec![0.0; WINDOW_LENGTH / 2];
+    let trimmed = cqt.process_trimmed(&signal, 512).unwrap();
+    assert_eq!(trimmed.dim(), (0, 108));
+  }
+
+  #[test]
+  fn test_process_planar() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+    let channels = Array2::from_shape_fn((2, signal.len()), |(_, n)| signal[n]);
+
+    let result = cqt.process_planar(channels.view(), hop_size).unwrap();
+    let expected = cqt.process(&signal, hop_size).unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0], expected);
+    assert_eq!(result[1], expected);
+
+    // Column-major channels give the same result
+    let channels = Array2::from_shape_fn((2, signal.len()).f(), |(_, n)| signal[n]);
+    let result = cqt.process_planar(channels.view(), hop_size).unwrap();
+    assert_eq!(result[1], expected);
+  }
+
+  #[test]
+  fn test_process_planar_no_channel() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let channels = Array2::<f32>::zeros((0, 1024));
+    let result = cqt.process_planar(channels.view(), 512);
+    assert_eq!(result, Err(SignalError::EmptyInputSignal));
+  }
+
+  #[test]
+  fn test_process_mag_and_power() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_single_sided(true);
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    let (magnitude, power) = cqt.process_mag_and_power(&signal, hop_size).unwrap();
+
+    assert_eq!(magnitude, cqt.process(&signal, hop_size).unwrap());
+
+    Zip::from(&magnitude)
+      .and(&power)
+      .for_each(|&magnitude_elem, &power_elem| {
+        assert_relative_eq!(power_elem, magnitude_elem * magnitude_elem, max_relative = 1e-4);
+      });
+  }
+
+  #[test]
+  fn test_stream_chroma() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    // Long enough to span several chunks
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 220.0, 2.0);
+
+    for hop_size in [512, 2048] {
+      let chromagram = cqt.stream_chroma(&signal, hop_size).unwrap();
+      let matrix = cqt.process(&signal, hop_size).unwrap();
+      let expected = chroma(&matrix, cqt.cqt_params()).unwrap();
+
+      assert_eq!(chromagram.dim(), expected.dim());
+
+      Zip::from(&chromagram)
+        .and(&expected)
+        .for_each(|&chroma_elem, &expected_elem| {
+          assert_abs_diff_eq!(chroma_elem, expected_elem, epsilon = 1e-4 * expected_elem.max(1.0));
+        });
+    }
+  }
+
+  #[test]
+  fn test_process_planar_complex() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+    let zeros = vec![0.0; signal.len()];
+
+    let result = cqt.process_planar_complex(&signal, &zeros, hop_size).unwrap();
+    assert_eq!(result, cqt.process(&signal, hop_size).unwrap());
+
+    // The imaginary part alone has the same magnitude, up to the rounding of the complex FFT
+    let result = cqt.process_planar_complex(&zeros, &signal, hop_size).unwrap();
+    Zip::from(&result)
+      .and(&cqt.process(&signal, hop_size).unwrap())
+      .for_each(|&result_elem, &expected_elem| {
+        assert_relative_eq!(result_elem, expected_elem, max_relative = 1e-3, epsilon = 1.0);
+      });
+  }
+
+  #[test]
+  fn test_process_analytic() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let positive_cqt = Cqt::new(new_params().with_phase_convention(PhaseConvention::Positive));
+    let hop_size = 2048;
+
+    // A low cosine, whose mirror image at -5 Hz falls within the lowest filters
+    let signal: Vec<f32> = (0..SAMPLE_RATE)
+      .map(|n| (2.0 * PI * 5.0 * (n as f32) / (SAMPLE_RATE as f32)).cos())
+      .collect();
+
+    let result = cqt.process(&signal, hop_size).unwrap();
+    let analytic_result = cqt.process_analytic(&signal, hop_size).unwrap();
+    // Relative spread of the lowest bin over the frames away from the signal edges
+    let spread = |matrix: &Array2<f32>| {
+      let bin = matrix.slice(s![4..matrix.nrows() - 4, 0]);
+      let max = bin.fold(f32::MIN, |max, &value| max.max(value));
+      let min = bin.fold(f32::MAX, |min, &value| min.min(value));
+
+      (max - min) / bin.mean().unwrap()
+    };
+
+    // The tone beats against its mirror image with the real input only
+    assert!(spread(&result) > 0.1);
+    assert!(spread(&analytic_result) < 1e-3);
+
+    // The analytic signal is taken on the side of the filters
+    let positive_result = positive_cqt.process_analytic(&signal, hop_size).unwrap();
+    Zip::from(&positive_result)
+      .and(&analytic_result)
+      .for_each(|&positive_elem, &analytic_elem| {
+        assert_relative_eq!(positive_elem, analytic_elem, max_relative = 1e-3, epsilon = 1.0);
+      });
+  }
+
+  #[test]
+  fn test_process_planar_complex_matches_complex_parts() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let single_sided_cqt = Cqt::new(new_params().with_single_sided(true));
+    let hop_size = 2048;
+    let re = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+    let im = create_dummy_audio_signal(SAMPLE_RATE, 1000.0, 0.5);
+
+    // The single pass matches combining the complex CQT of each part
+    let mut expected = cqt.process_complex(&re, hop_size).unwrap();
+    Zip::from(&mut expected)
+      .and(&cqt.process_complex(&im, hop_size).unwrap())
+      .for_each(|expected_elem, &im_elem| {
+        *expected_elem += Complex::<f32>::i() * im_elem;
+      });
+    let result = cqt.process_planar_complex(&re, &im, hop_size).unwrap();
+
+    Zip::from(&result)
+      .and(&expected)
+      .for_each(|&result_elem, &expected_elem| {
+        assert_relative_eq!(result_elem, expected_elem.norm(), max_relative = 1e-3, epsilon = 1.0);
+      });
+
+    // The single-sided scaling of real signals doesn't apply to complex ones
+    assert_eq!(single_sided_cqt.process_planar_complex(&re, &im, hop_size).unwrap(), result);
+  }
+
+  #[test]
+  fn test_process_planar_complex_length_mismatch() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    let result = cqt.process_planar_complex(&[0.0; 1024], &[0.0; 1023], 512);
+    assert_eq!(result, Err(SignalError::ChannelLengthMismatch));
+  }
+
+  #[test]
+  fn test_process_polar() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 2048;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    let (magnitude, phase) = cqt.process_polar(&signal, hop_size).unwrap();
+    let expected_magnitude = cqt.process(&signal, hop_size).unwrap();
+    let expected_complex = cqt.process_complex(&signal, hop_size).unwrap();
+
+    assert_eq!(magnitude, expected_magnitude);
+
+    Zip::from(&magnitude)
+      .and(&phase)
+      .and(&expected_complex)
+      .for_each(|&magnitude_elem, &phase_elem, expected_elem| {
+        let reconstructed = Complex::from_polar(magnitude_elem, phase_elem);
+        let tolerance = 1e-4 * expected_elem.norm().max(1.0);
+
+        assert_abs_diff_eq!(reconstructed.re, expected_elem.re, epsilon = tolerance);
+        assert_abs_diff_eq!(reconstructed.im, expected_elem.im, epsilon = tolerance);
+      });
+  }
+
+  #[test]
+  fn test_process_octave_windows_high_frequency_tone() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let octave_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_octave_windows(true);
+    let cqt = Cqt::new(cqt_params);
+    let octave_cqt = Cqt::new(octave_cqt_params);
+
+    // A steady tone at the center of a high bin
+    let bin_index = 7 * BINS_PER_OCTAVE + 3;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * std::f32::consts::PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+    let hop_size = 1024;
+
+    let result = cqt.process(&signal, hop_size).unwrap();
+    let octave_result = octave_cqt.process(&signal, hop_size).unwrap();
+    assert_eq!(octave_result.dim(), result.dim());
+
+    // Both peak in the tone bin of a fully voiced frame with roughly the same magnitude
+    let frame = result.row(result.nrows() / 2);
+    let octave_frame = octave_result.row(octave_result.nrows() / 2);
+    let peak_bin = |row: ArrayView1<f32>| {
+      row
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > row[peak] { idx } else { peak })
+    };
+
+    assert_eq!(peak_bin(frame), bin_index);
+    assert_eq!(peak_bin(octave_frame), bin_index);
+    assert_relative_eq!(octave_frame[bin_index], frame[bin_index], max_relative = 0.1);
+  }
+
+  #[test]
+  fn test_frequency_response_peaks_at_center_freq() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let bin = 60;
+    let num_points = SAMPLE_RATE / 20 + 1;
+
+    let response = cqt.frequency_response(bin, num_points);
+    let peak_point = response
+      .iter()
+      .enumerate()
+      .fold(0, |peak, (idx, &value)| if value > response[peak] { idx } else { peak });
+
+    // The grid has a 10 Hz resolution
+    let peak_freq = (peak_point as f32) * 10.0;
+    assert_eq!(response.len(), num_points);
+    assert_abs_diff_eq!(peak_freq, cqt.cqt_params.center_freq(bin), epsilon = 5.0);
+  }
+
+  #[test]
+  fn test_frequency_response_octave_windows() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let octave_cqt = Cqt::new(new_params().with_octave_windows(true));
+    let num_points = SAMPLE_RATE / 20 + 1;
+
+    // The lowest octave keeps the full window
+    assert_eq!(octave_cqt.frequency_response(5, num_points), cqt.frequency_response(5, num_points));
+
+    // The shorter window of the highest octave widens the response of its bins
+    let bin = 100;
+    let response = cqt.frequency_response(bin, num_points);
+    let octave_response = octave_cqt.frequency_response(bin, num_points);
+    let bandwidth = |response: &Array1<f32>| {
+      let peak = response.fold(0.0f32, |max, &value| max.max(value));
+      response.iter().filter(|&&value| value > peak / 2.0).count()
+    };
+    assert!(bandwidth(&octave_response) > 2 * bandwidth(&response));
+
+    // The response follows the gain of the octave, relative to the coherent gains
+    let gain_ratio = |cqt: &Cqt, bin: usize| {
+      // A grid holding the center frequency of the bin
+      let freq_step = cqt.cqt_params.center_freq(bin) / 50.0;
+      let num_points = ((SAMPLE_RATE as f32) / 2.0 / freq_step) as usize + 1;
+      let point = (cqt.cqt_params.center_freq(bin) / freq_step).round() as usize;
+
+      cqt.coherent_gains()[bin] / cqt.frequency_response(bin, num_points)[point]
+    };
+    assert_relative_eq!(
+      gain_ratio(&octave_cqt, bin),
+      gain_ratio(&octave_cqt, 5),
+      max_relative = 0.05
+    );
+  }
+
+  #[test]
+  fn test_filterbank_condition() {
+    // Bins spaced closer than the FFT resolution overlap evenly
+    let cqt_params = CQTParams::new(MIN_FREQ, 320.0, 24, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let condition = Cqt::new(cqt_params).filterbank_condition();
+    assert!(condition.is_finite());
+    assert!(condition < 20.0);
+
+    // The fixed window leaves gaps between the high bins
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    assert!(Cqt::new(cqt_params).filterbank_condition() > 1e3);
+  }
+
+  #[test]
+  fn test_filterbank_condition_no_fft_frequency() {
+    // The first bin is above the highest FFT frequency of the window, 44089.2 Hz
+    let cqt_params = CQTParams::new(44090.0, 44095.0, 12, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+
+    assert_eq!(Cqt::new(cqt_params).filterbank_condition(), f32::INFINITY);
+  }
+
+  #[test]
+  fn test_latency_samples() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    for hop_size in [512, 1024, 2048] {
+      let latency = cqt.latency_samples(hop_size);
+      assert_eq!(latency, (hop_size / 2) as isize);
+
+      // An impulse at the center of frame 10
+      let impulse_frame = 10;
+      let mut signal = vec![0.0; 24 * hop_size];
+      signal[((impulse_frame * hop_size) as isize + latency) as usize] = 1.0;
+
+      let result = cqt.process(&signal, hop_size).unwrap();
+      let frame_energy = result.sum_axis(Axis(1));
+      let peak_frame = frame_energy
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > frame_energy[peak] { idx } else { peak });
+
+      assert_eq!(peak_frame, impulse_frame);
+    }
+  }
+
+  #[test]
+  fn test_causal_centering_impulse() {
+    let hop_size = 512;
+    let impulse_idx = 20 * hop_size + 100;
+    let mut signal = vec![0.0; 48 * hop_size];
+    signal[impulse_idx] = 1.0;
+
+    let centered_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let causal_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_centering(Centering::Causal);
+    assert_eq!(centered_params.centering(), Centering::Centered);
+
+    let centered = Cqt::new(centered_params).process(&signal, hop_size).unwrap();
+    let causal = Cqt::new(causal_params).process(&signal, hop_size).unwrap();
+    assert_eq!(causal.dim(), centered.dim());
+
+    // Frame `k` ends at sample `(k + 1) * hop_size` and never sees the impulse before it occurs
+    for k in 0..causal.nrows() {
+      if (k + 1) * hop_size <= impulse_idx {
+        assert!(causal.row(k).iter().all(|&value| value == 0.0));
+      }
+    }
+    // The centered frames look ahead of the impulse
+    let frame_before = impulse_idx / hop_size - 1;
+    assert!(centered.row(frame_before).sum() > 0.0);
+    assert!(causal.row(impulse_idx / hop_size).sum() > 0.0);
+  }
+
+  #[test]
+  fn test_process_dbfs() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let floor_db = -120.0;
+
+    // A full scale and a half scale sine at the center of a bin
+    let bin_index = 60;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let sine = |amplitude: f32| -> Vec<f32> {
+      (0..SAMPLE_RATE / 2)
+        .map(|n| amplitude * (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+        .collect()
+    };
 
-use crate::{ CQTParams, compute_cqt_filterbank };
-use input_signal::pad_input_signal;
+    let result = cqt.process_dbfs(&sine(1.0), 2048, floor_db).unwrap();
+    let frame = result.row(result.nrows() / 2);
+    assert_abs_diff_eq!(frame[bin_index], 0.0, epsilon = 0.1);
+    assert!(frame.iter().all(|&value| value >= floor_db && value <= 0.1));
 
-pub use cqt_signal_error_enum::SignalError;
+    let result = cqt.process_dbfs(&sine(0.5), 2048, floor_db).unwrap();
+    assert_abs_diff_eq!(result[[result.nrows() / 2, bin_index]], -6.02, epsilon = 0.1);
 
-/// The Cqt struct is an implementation of the Constant Q Transform (CQT)
-/// for time-frequency analysis of a signal. The struct provides methods to
-/// initialize the CQT parameters and compute the CQT of a given input signal.
-pub struct Cqt {
-  cqt_params: CQTParams,
-  pub filterbank: Array2<Complex<f32>>,
-}
+    let result = cqt.process_dbfs(&vec![0.0; 4096], 2048, floor_db).unwrap();
+    assert!(result.iter().all(|&value| value == floor_db));
+  }
 
-impl Cqt {
-  /// Constructs a new `Cqt` instance with the given parameters.
-  ///
-  /// # Arguments
-  ///
-  /// * `cqt_params` - CQTParams
-  ///
-  /// # Returns
-  ///
-  /// A new `Cqt` instance with the specified parameters.
-  pub fn new(cqt_params: CQTParams) -> Self {
-    // Compute the CQT filterbank using the CQTParams instance
-    let filterbank = compute_cqt_filterbank(&cqt_params).expect("Error computing CQT filterbank");
+  #[test]
+  fn test_coherent_gains() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
 
-    // Return a new Cqt instance with the given parameters and filterbank
-    Cqt {
-      cqt_params,
-      filterbank,
+    for cqt in [Cqt::new(new_params()), Cqt::new(new_params().with_octave_windows(true))] {
+      let num_bins = cqt.filterbank.nrows();
+      let sample_rate = SAMPLE_RATE as f32;
+
+      // The gains are computed on the first use only
+      assert!(cqt.coherent_gains.get().is_none());
+      let coherent_gains = cqt.coherent_gains().clone();
+      assert!(cqt.coherent_gains.get().is_some());
+
+      // They match the output of each bin when processing the sine at its center frequency
+      let sines = Array2::from_shape_fn((num_bins, WINDOW_LENGTH), |(bin, n)| {
+        let center_freq = cqt.cqt_params.center_freq(bin);
+        ((2.0 * PI * center_freq * (n as f32)) / sample_rate).sin()
+      });
+      let cqt_sines = cqt.compute_cqt_frames(num_bins, true, |bin| sines.row(bin)).unwrap();
+
+      Zip::from(&coherent_gains)
+        .and(cqt_sines.diag())
+        .for_each(|&gain, &expected| {
+          assert_relative_eq!(gain, expected.norm(), max_relative = 1e-3);
+        });
     }
   }
 
-  /// Process the input signal and compute the Constant-Q Transform (CQT) features.
-  ///
-  /// # Arguments
-  ///
-  /// * `input_signal` - An Array1<f32> of the input audio signal
-  /// * `hop_size` - The number of samples to hop between frames
-  ///
-  /// # Returns
-  ///
-  /// * `Result<Array2<f32>, SignalError>` - The CQT feature matrix
-  pub fn process(&self, signal: &[f32], hop_size: usize) -> Result<Array2<f32>, SignalError> {
-    let signal_len = signal.len();
+  #[test]
+  fn test_project_frames() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
 
-    if signal_len == 0 {
-      return Err(SignalError::EmptyInputSignal);
+    let fft_frames = Array2::from_shape_fn((4, WINDOW_LENGTH), |(frame_idx, n)| {
+      let phase = 0.01 * ((frame_idx * WINDOW_LENGTH + n) as f32);
+      Complex::new(phase.sin(), phase.cos())
+    });
+    let result = project_frames(&fft_frames, &cqt.filterbank);
+
+    for ((frame_idx, bin), &value) in result.indexed_iter() {
+      let expected: Complex<f32> = fft_frames
+        .row(frame_idx)
+        .iter()
+        .zip(cqt.filterbank.row(bin).iter())
+        .map(|(&fft_elem, &filter_elem)| fft_elem * filter_elem)
+        .sum();
+
+      assert_relative_eq!(value.re, expected.re, epsilon = 1e-2, max_relative = 1e-4);
+      assert_relative_eq!(value.im, expected.im, epsilon = 1e-2, max_relative = 1e-4);
     }
+  }
 
-    if hop_size == 0 || hop_size > self.cqt_params.window_length {
-      return Err(SignalError::InvalidHopSize);
+  #[test]
+  fn test_export_frames() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+
+    let (frames, filterbank_re, filterbank_im) = cqt.export_frames(&signal, 2048).unwrap();
+    let expected = cqt.process(&signal, 2048).unwrap();
+    assert_eq!(frames.dim(), (expected.nrows(), WINDOW_LENGTH));
+    assert_eq!(filterbank_re.dim(), cqt.filterbank.dim());
+    assert!(frames.is_standard_layout() && filterbank_re.is_standard_layout());
+    assert!(matches!(
+      Cqt::new(new_params().with_octave_windows(true)).export_frames(&signal, 2048),
+      Err(SignalError::OctaveWindowsUnsupported)
+    ));
+
+    // Transform and project the exported frames on the CPU
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(WINDOW_LENGTH);
+    let mut fft_frames = frames.mapv(|x| Complex::new(x, 0.0));
+    for mut row in fft_frames.axis_iter_mut(Axis(0)) {
+      fft.process(row.as_slice_mut().unwrap());
     }
+    let filterbank = Zip::from(&filterbank_re)
+      .and(&filterbank_im)
+      .map_collect(|&re, &im| Complex::new(re, im));
+    let result = project_frames(&fft_frames, &filterbank).mapv(|x| x.abs());
 
-    let num_frames = signal_len / hop_size;
+    let tolerance = 1e-5 * expected.fold(0.0f32, |max, &value| max.max(value));
+    Zip::from(&result)
+      .and(&expected)
+      .for_each(|&value, &expected_value| {
+        assert_abs_diff_eq!(value, expected_value, epsilon = tolerance);
+      });
+  }
 
-    let window_len = self.cqt_params.window_length;
-    let hann_window = &self.cqt_params.hann_window;
-    let transposed_filterbank = self.filterbank.t();
+  #[test]
+  fn test_write_response_csv() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let path = env::temp_dir().join("cqt_rs_test_response.csv");
+    let num_points = 16;
 
-    // Assign the input signal to the center of the padded signal
-    let signal_padded = pad_input_signal(signal, window_len, hop_size).expect(
-      "Error padding input signal"
-    );
+    cqt.write_response_csv(&path, num_points).unwrap();
+    let csv = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
 
-    // Initialize the matrix to store the FFT output for each frame
-    let mut cqt_output = Array2::<Complex<f32>>::zeros((num_frames, window_len));
-    let fft = FftPlanner::<f32>::new().plan_fft_forward(window_len);
+    let mut lines = csv.lines();
+    let header: Vec<&str> = lines.next().unwrap().split(',').collect();
+    assert_eq!(header.len(), num_points + 1);
+    assert_eq!(header[0], "bin");
 
-    // Compute the CQT for each frame
-    cqt_output
-      .axis_iter_mut(Axis(0))
-      .into_par_iter()
-      .enumerate()
-      .for_each(|(frame_idx, mut fft_output_row)| {
-        let start = frame_idx * hop_size;
-        let end = start + window_len;
+    let rows: Vec<Vec<f32>> = lines
+      .map(|line| {
+        line
+          .split(',')
+          .map(|value| value.parse().unwrap())
+          .collect()
+      })
+      .collect();
+    assert_eq!(rows.len(), cqt.cqt_params.num_bins());
 
-        // Get the frame from the padded signal
-        let frame = signal_padded.slice(s![start..end]);
+    let expected = cqt.frequency_response(60, num_points);
+    assert_eq!(rows[60][0], 60.0);
+    assert_eq!(&rows[60][1..], expected.as_slice().unwrap());
+  }
 
-        // Perform element-wise multiplication of the frame with the Hann window,
-        // and store the result in the fft_output_row
-        Zip::from(&mut fft_output_row)
-          .and(frame)
-          .and(hann_window)
-          .par_for_each(|row_elem, &frame_elem, &window_elem| {
-            row_elem.re = frame_elem * window_elem;
-          });
+  #[test]
+  fn test_compute_magnitudes() {
+    let cqt_output = Array2::from_shape_fn((64, 108), |(frame_idx, bin)| {
+      let phase = 0.01 * ((frame_idx * 108 + bin) as f32);
+      Complex::new(phase.sin(), 2.0 * phase.cos())
+    });
 
-        // Perform FFT
-        fft.process(fft_output_row.as_slice_mut().expect("Error applying fft to frame"));
-      });
+    let sequential = compute_magnitudes(&cqt_output, false);
+    let parallel = compute_magnitudes(&cqt_output, true);
+    assert_eq!(sequential, parallel);
+    assert_eq!(magnitudes(&cqt_output), sequential);
+    assert_eq!(sequential[[1, 2]], cqt_output[[1, 2]].norm());
+  }
+
+  #[test]
+  fn test_process_remove_dc() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let dc_cqt = Cqt::new(new_params().with_remove_dc(true));
 
-    // Apply the CQT filterbank to the FFT output matrix
-    let cqt_filtered = cqt_output.dot(&transposed_filterbank);
+    // A tone at the center of a bin riding on a large DC offset
+    let freq = cqt.cqt_params.center_freq(60);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| 2.0 + (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
 
-    // Compute the element-wise absolute value of the filtered CQT matrix NOTE: check if needed to be done later
-    let abs_cqt_filtered = cqt_filtered.mapv(|x| x.abs());
+    let result = cqt.process(&signal, 2048).unwrap();
+    let dc_result = dc_cqt.process(&signal, 2048).unwrap();
+    let frame = result.nrows() / 2;
+    let low_energy = |matrix: &Array2<f32>| {
+      matrix
+        .slice(s![frame, ..BINS_PER_OCTAVE])
+        .iter()
+        .map(|&value| value * value)
+        .sum::<f32>()
+    };
 
-    // Just in case tested the parallel version and it's slower
-    // let mut abs_cqt_filtered = Array2::<f32>::zeros(cqt_filtered.dim());
-    // par_azip!((abs_cqt_filtered_row in &mut abs_cqt_filtered, cqt_filtered_row in &cqt_filtered) {
-    //   *abs_cqt_filtered_row = cqt_filtered_row.abs();
-    // });
+    assert!(low_energy(&dc_result) < 1e-3 * low_energy(&result));
 
-    Ok(abs_cqt_filtered)
+    // The tone itself is kept
+    assert_relative_eq!(dc_result[[frame, 60]], result[[frame, 60]], max_relative = 1e-2);
   }
-}
 
-#[cfg(test)]
-mod tests {
-  use approx::assert_abs_diff_eq;
+  #[test]
+  fn test_process_pre_emphasis() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let emphasized_cqt = Cqt::new(new_params().with_pre_emphasis(Some(0.97)));
 
-  use crate::create_dummy_audio_signal;
+    // A broadband impulse train
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| if n % 1000 == 0 { 1.0 } else { 0.0 })
+      .collect();
 
-  use super::*;
+    let result = cqt.process(&signal, 2048).unwrap();
+    let emphasized_result = emphasized_cqt.process(&signal, 2048).unwrap();
+    // Share of the energy in the highest octave
+    let high_energy_ratio = |matrix: &Array2<f32>| {
+      let energy = matrix.mapv(|value| value * value);
+      let num_bins = energy.ncols();
 
-  const MIN_FREQ: f32 = 20.0;
-  const MAX_FREQ: f32 = 7902.1;
-  const BINS_PER_OCTAVE: usize = 12;
-  const SAMPLE_RATE: usize = 44100;
-  const WINDOW_LENGTH: usize = 4096;
+      energy.slice(s![.., num_bins - BINS_PER_OCTAVE..]).sum() / energy.sum()
+    };
+
+    assert!(high_energy_ratio(&emphasized_result) > 2.0 * high_energy_ratio(&result));
+  }
 
   #[test]
-  fn test_new_cqt() {
+  fn test_pre_emphasis_entry_points() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_pre_emphasis(Some(0.97));
+    let cqt = Cqt::new(cqt_params);
+    let hop_size = 1024;
+    // More frames than a single chunk
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 2.5);
+    let expected = cqt.process(&signal, hop_size).unwrap();
+    assert!(expected.nrows() > STREAM_CHUNK_FRAMES);
+
+    let assert_close = |result: ArrayView2<f32>, expected: ArrayView2<f32>| {
+      assert_eq!(result.dim(), expected.dim());
+      Zip::from(result)
+        .and(expected)
+        .for_each(|&value, &expected_value| {
+          assert_abs_diff_eq!(value, expected_value, epsilon = 1e-4 * expected_value.max(1.0));
+        });
+    };
+
+    let chromagram = cqt.stream_chroma(&signal, hop_size).unwrap();
+    assert_close(chromagram.view(), chroma(&expected, cqt.cqt_params()).unwrap().view());
+
+    let mut frames = Array2::<f32>::zeros(expected.dim());
+    cqt
+      .for_each_frame(&signal, hop_size, |frame_idx, frame| {
+        frames.row_mut(frame_idx).assign(&frame);
+      })
+      .unwrap();
+    assert_close(frames.view(), expected.view());
+
+    let frames: Vec<Array1<f32>> = cqt
+      .frames_from_iter(signal.iter().copied(), hop_size)
+      .unwrap()
+      .collect();
+    let frames = ndarray::stack(
+      Axis(0),
+      &frames.iter().map(|frame| frame.view()).collect::<Vec<_>>()
+    ).unwrap();
+    assert_close(frames.view(), expected.view());
+
+    // The filter carries the sample before the region
+    let region = cqt.process_region(&signal, 10 * hop_size, 20 * hop_size, hop_size).unwrap();
+    assert_close(region.view(), expected.slice(s![10..30, ..]));
+
+    // A full scale sine still reads 0 dBFS at the center of its bin
+    let bin_index = 60;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let sine: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+    let result = cqt.process_dbfs(&sine, 2048, -120.0).unwrap();
+    assert_abs_diff_eq!(result[[result.nrows() / 2, bin_index]], 0.0, epsilon = 0.1);
+  }
+
+  #[test]
+  fn test_project_frames_tiled() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      36,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let filterbank = compute_cqt_filterbank(&cqt_params).unwrap();
+    assert!(filterbank.nrows() >= PARALLEL_PROJECTION_BINS);
+
+    let fft_frames = Array2::from_shape_fn((8, WINDOW_LENGTH), |(frame_idx, n)| {
+      let phase = 0.01 * ((frame_idx * WINDOW_LENGTH + n) as f32);
+      Complex::new(phase.sin(), phase.cos())
+    });
+
+    let expected = project_frames_with(&fft_frames, &filterbank, false);
+    assert_eq!(project_frames_with(&fft_frames, &filterbank, true), expected);
+    assert_eq!(project_frames(&fft_frames, &filterbank), expected);
+  }
+
+  #[test]
+  fn test_process_silent_signal() {
     let cqt_params = CQTParams::new(
       MIN_FREQ,
       MAX_FREQ,
@@ -145,12 +3210,24 @@ mod tests {
       WINDOW_LENGTH
     ).unwrap();
     let cqt = Cqt::new(cqt_params);
+    let signal = vec![0.0; SAMPLE_RATE];
+
+    let result = cqt.process(&signal, 2048).unwrap();
+    assert_eq!(result, Array2::<f32>::zeros((SAMPLE_RATE / 2048, cqt.cqt_params.num_bins())));
 
-    assert_eq!(cqt.filterbank.dim(), (108, 4096));
+    // The silent frames of a non-silent signal are zeros as well
+    let mut impulse = signal.clone();
+    impulse[0] = 1.0;
+    let result = cqt.process(&impulse, 2048).unwrap();
+    assert!(result.slice(s![4.., ..]).iter().all(|&value| value == 0.0));
+
+    // The invalid inputs are still rejected
+    assert_eq!(cqt.process(&signal, 0), Err(SignalError::InvalidHopSize));
+    assert_eq!(cqt.process(&[], 2048), Err(SignalError::EmptyInputSignal));
   }
 
   #[test]
-  fn test_process_valid_signal() {
+  fn test_for_each_frame() {
     let cqt_params = CQTParams::new(
       MIN_FREQ,
       MAX_FREQ,
@@ -159,17 +3236,58 @@ mod tests {
       WINDOW_LENGTH
     ).unwrap();
     let cqt = Cqt::new(cqt_params);
+    // More frames than a single chunk
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 2.5);
+    let expected = cqt.process(&signal, 1024).unwrap();
+    assert!(expected.nrows() > STREAM_CHUNK_FRAMES);
 
-    let signal = vec![0.0; 1024];
-    let hop_size = 512;
-    let result = cqt.process(&signal, hop_size);
-    assert!(result.is_ok());
-    let cqt_features = result.unwrap();
-    assert_eq!(cqt_features.dim(), (2, 108));
+    let mut frames = Vec::new();
+    cqt
+      .for_each_frame(&signal, 1024, |frame_idx, frame| {
+        assert_eq!(frame_idx, frames.len());
+        frames.push(frame.to_owned());
+      })
+      .unwrap();
+
+    assert_eq!(frames.len(), expected.nrows());
+    for (frame, expected_frame) in frames.iter().zip(expected.outer_iter()) {
+      Zip::from(frame)
+        .and(expected_frame)
+        .for_each(|&value, &expected_value| {
+          assert_abs_diff_eq!(value, expected_value, epsilon = 1e-4 * expected_value.max(1.0));
+        });
+    }
+
+    assert_eq!(cqt.for_each_frame(&signal, 0, |_, _| {}), Err(SignalError::InvalidHopSize));
   }
 
   #[test]
-  fn test_process_sine_wave() {
+  fn test_unity_peak_filter_norm() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH)
+        .unwrap()
+        .with_filter_norm(FilterNorm::UnityPeak)
+    };
+    let cqt = Cqt::new(new_params());
+    let octave_cqt = Cqt::new(new_params().with_octave_windows(true));
+
+    // Unit-amplitude sines at the center of bins of low, middle and high octaves
+    for bin in [12, 60, 96] {
+      let freq = cqt.cqt_params.center_freq(bin);
+      let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+        .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+        .collect();
+
+      let result = cqt.process(&signal, 2048).unwrap();
+      let octave_result = octave_cqt.process(&signal, 2048).unwrap();
+      let frame = result.nrows() / 2;
+      assert_relative_eq!(result[[frame, bin]], 1.0, max_relative = 1e-2);
+      assert_relative_eq!(octave_result[[frame, bin]], 1.0, max_relative = 1e-2);
+    }
+  }
+
+  #[test]
+  fn test_process_region() {
     let cqt_params = CQTParams::new(
       MIN_FREQ,
       MAX_FREQ,
@@ -178,19 +3296,70 @@ mod tests {
       WINDOW_LENGTH
     ).unwrap();
     let cqt = Cqt::new(cqt_params);
-    let freq = 440.0;
-    let hop_size = 512;
-    let signal = create_dummy_audio_signal(SAMPLE_RATE, freq, 1.0);
-    let result = cqt.process(&signal, hop_size).unwrap();
+    let hop_size = 1024;
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 1.0);
 
-    let bin_index = ((freq / MIN_FREQ).log2() * (BINS_PER_OCTAVE as f32)).round() as usize;
-    let max_value = result.column(bin_index).iter().cloned().fold(f32::MIN, f32::max);
+    let full = cqt.process(&signal, hop_size).unwrap();
+
+    // A region on the hop grid matches the frames of the full signal
+    let first_frame = 10;
+    let region_frames = 20;
+    let start = first_frame * hop_size;
+    let len = region_frames * hop_size;
+
+    let region = cqt.process_region(&signal, start, len, hop_size).unwrap();
+    assert_eq!(region, full.slice(s![first_frame..first_frame + region_frames, ..]));
 
-    assert_abs_diff_eq!(max_value, 19386750.0, epsilon = 1e-2);
+    // Slicing instead pads the edges of the region with zeros
+    let sliced = cqt.process(&signal[start..start + len], hop_size).unwrap();
+    assert_eq!(sliced.dim(), region.dim());
+    assert_ne!(sliced.row(0), region.row(0));
+    assert_eq!(sliced.row(region_frames / 2), region.row(region_frames / 2));
+
+    // A region at the start of the signal is zero padded like the full signal
+    let region = cqt.process_region(&signal, 0, len, hop_size).unwrap();
+    assert_eq!(region, full.slice(s![..region_frames, ..]));
+
+    let result = cqt.process_region(&signal, signal.len() - len + 1, len, hop_size);
+    assert_eq!(result, Err(SignalError::InvalidRegion));
   }
 
   #[test]
-  fn test_process_empty_signal() {
+  fn test_frames_from_iter() {
+    let hop_size = 1024;
+
+    for (centering, signal_len) in [
+      (Centering::Centered, SAMPLE_RATE / 2),
+      (Centering::Centered, 2500),
+      (Centering::Causal, SAMPLE_RATE / 2),
+    ] {
+      let cqt_params = CQTParams::new(
+        MIN_FREQ,
+        MAX_FREQ,
+        BINS_PER_OCTAVE,
+        SAMPLE_RATE,
+        WINDOW_LENGTH
+      )
+        .unwrap()
+        .with_centering(centering);
+      let cqt = Cqt::new(cqt_params);
+      let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 1.0)[..signal_len].to_vec();
+
+      let expected = cqt.process(&signal, hop_size).unwrap();
+      let frames: Vec<Array1<f32>> = cqt
+        .frames_from_iter(signal.into_iter(), hop_size)
+        .unwrap()
+        .collect();
+
+      assert_eq!(frames.len(), expected.nrows());
+      for (frame, expected_frame) in frames.iter().zip(expected.outer_iter()) {
+        assert_eq!(frame, &expected_frame);
+      }
+    }
+  }
+
+  #[test]
+  fn test_frames_from_iter_edge_cases() {
     let cqt_params = CQTParams::new(
       MIN_FREQ,
       MAX_FREQ,
@@ -200,15 +3369,12 @@ mod tests {
     ).unwrap();
     let cqt = Cqt::new(cqt_params);
 
-    let signal = vec![];
-    let hop_size = 512;
-    let result = cqt.process(&signal, hop_size);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), SignalError::EmptyInputSignal);
+    assert_eq!(cqt.frames_from_iter(std::iter::empty(), 1024).unwrap().count(), 0);
+    assert!(cqt.frames_from_iter(std::iter::empty(), 0).is_err());
   }
 
   #[test]
-  fn test_process_invalid_hop_size() {
+  fn test_process_topk() {
     let cqt_params = CQTParams::new(
       MIN_FREQ,
       MAX_FREQ,
@@ -218,10 +3384,183 @@ mod tests {
     ).unwrap();
     let cqt = Cqt::new(cqt_params);
 
-    let signal = vec![0.0; 128];
-    let hop_size = 0;
-    let result = cqt.process(&signal, hop_size);
-    assert!(result.is_err());
-    assert_eq!(result.unwrap_err(), SignalError::InvalidHopSize);
+    // A tone at the center of a bin
+    let bin_index = 60;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let magnitudes = cqt.process(&signal, 2048).unwrap();
+    let result = cqt.process_topk(&signal, 2048, 1).unwrap();
+    assert_eq!(result.len(), magnitudes.nrows());
+
+    for (frame_idx, frame) in result.iter().enumerate() {
+      assert_eq!(frame, &vec![(bin_index, magnitudes[[frame_idx, bin_index]])]);
+    }
+
+    // The bins are sorted by descending magnitude, up to the number of bins
+    let result = cqt.process_topk(&signal, 2048, 1000).unwrap();
+    for frame in &result {
+      assert_eq!(frame.len(), magnitudes.ncols());
+      assert!(frame.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+  }
+
+  #[test]
+  fn test_process_log() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let epsilon = 1e-6;
+
+    // A silent frame maps uniformly to ln(epsilon)
+    let result = cqt.process_log(&vec![0.0; 4096], 2048, epsilon).unwrap();
+    assert!(result.iter().all(|&value| value == epsilon.ln()));
+
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+    let magnitude = cqt.process(&signal, 2048).unwrap();
+    let result = cqt.process_log(&signal, 2048, epsilon).unwrap();
+    assert_eq!(result, magnitude.mapv(|x| (x + epsilon).ln()));
+  }
+
+  #[test]
+  fn test_process_time_resolution() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 1.0);
+
+    // 10 ms at 44100 Hz is a hop of 441 samples, so one second yields 100 frames
+    let (result, hop_size) = cqt.process_time_resolution(&signal, 0.01).unwrap();
+    assert_eq!(hop_size, 441);
+    assert_eq!(result.dim(), (100, cqt.cqt_params.num_bins()));
+    assert_eq!(result, cqt.process(&signal, 441).unwrap());
+
+    // The hop size is clamped to the window length
+    let (_, hop_size) = cqt.process_time_resolution(&signal, 1.0).unwrap();
+    assert_eq!(hop_size, WINDOW_LENGTH);
+    let (_, hop_size) = cqt.process_time_resolution(&signal[..16], 0.0).unwrap();
+    assert_eq!(hop_size, 1);
+  }
+
+  #[test]
+  fn test_process_checked() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 1.0);
+    let short_signal = &signal[..100];
+
+    assert_eq!(
+      cqt.process_checked(short_signal, 50, true),
+      Err(SignalError::SignalTooShortForWindow { len: 100, window_length: WINDOW_LENGTH })
+    );
+
+    // The short signal is zero-padded by default
+    let result = cqt.process_checked(short_signal, 50, false).unwrap();
+    assert_eq!(result, cqt.process(short_signal, 50).unwrap());
+    assert_eq!(result.nrows(), 2);
+
+    let result = cqt.process_checked(&signal[..WINDOW_LENGTH], 2048, true).unwrap();
+    assert_eq!(result, cqt.process(&signal[..WINDOW_LENGTH], 2048).unwrap());
+  }
+
+  #[test]
+  fn test_cola_gain() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // The squared Hann window is COLA at a quarter of the window
+    let cola_gain = cqt.cola_gain(WINDOW_LENGTH / 4).unwrap();
+    assert_eq!(cola_gain.len(), WINDOW_LENGTH);
+    for &gain in cola_gain.iter() {
+      assert_relative_eq!(gain, 1.5, max_relative = 1e-2);
+    }
+
+    // But not at half of the window
+    let cola_gain = cqt.cola_gain(WINDOW_LENGTH / 2).unwrap();
+    let min_gain = cola_gain.iter().cloned().fold(f32::MAX, f32::min);
+    let max_gain = cola_gain.iter().cloned().fold(f32::MIN, f32::max);
+    assert_relative_eq!(min_gain / max_gain, 0.5, max_relative = 1e-2);
+
+    assert_eq!(cqt.cola_gain(0), Err(SignalError::InvalidHopSize));
+  }
+
+  #[test]
+  fn test_cola_gain_synth_window() {
+    let new_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let cqt = Cqt::new(new_params());
+    let synth_cqt = Cqt::new(new_params().with_synth_window(vec![1.0; WINDOW_LENGTH]).unwrap());
+
+    // The relative error of a reconstruction divided by the mean gain instead of the gain
+    let ripple = |cola_gain: Array1<f32>| {
+      let mean_gain = cola_gain.mean().unwrap();
+      cola_gain.fold(0.0f32, |max, &gain| max.max((gain / mean_gain - 1.0).abs()))
+    };
+
+    // The Hann analysis window is COLA by itself at half of the window
+    let hop_size = WINDOW_LENGTH / 2;
+    let synth_ripple = ripple(synth_cqt.cola_gain(hop_size).unwrap());
+    assert!(synth_ripple < 1e-3);
+    assert!(synth_ripple < ripple(cqt.cola_gain(hop_size).unwrap()));
+  }
+
+  #[test]
+  fn test_process_single_sided() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_single_sided(true);
+    let cqt = Cqt::new(cqt_params);
+
+    // A unit-amplitude sine at the center of a bin
+    let bin_index = 60;
+    let freq = cqt.cqt_params.center_freq(bin_index);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+    let result = cqt.process(&signal, 2048).unwrap();
+
+    // The full energy of the sine: window length * Q * norm * sum(hann^2)
+    let sum_of_squares: f32 = cqt.cqt_params.hann_window
+      .iter()
+      .map(|x| x * x)
+      .sum();
+    let expected =
+      (WINDOW_LENGTH as f32) *
+      cqt.cqt_params.q_factor() *
+      cqt.cqt_params.norm_factor() *
+      sum_of_squares;
+
+    assert_relative_eq!(result[[result.nrows() / 2, bin_index]], expected, max_relative = 1e-3);
   }
 }
\ No newline at end of file
@@ -0,0 +1,315 @@
+use ndarray::{ Array2, ArrayView1, Zip, s };
+use rustfft::{ num_complex::Complex, FftPlanner };
+use std::f32::consts::PI;
+
+use super::{
+  compute_fft_frames,
+  input_signal::pad_input_signal,
+  magnitudes,
+  project_frames,
+  Cqt,
+  SignalError,
+};
+
+/// The filterbanks of `Cqt::process_reassigned` for unwindowed frames: the frame kernels,
+/// their time-weighted kernels and their derivative kernels.
+pub(super) type ReassignmentFilterbanks = [Array2<Complex<f32>>; 3];
+
+impl Cqt {
+  /// Process the input signal and compute the reassigned Constant-Q Transform (CQT) features.
+  ///
+  /// The magnitude of each frame and bin is moved to the frame and bin of its center of
+  /// gravity, sharpening the spectrogram of steady tones and transients. With `h` the frame
+  /// kernel of a bin, the Hann window times its filter, and `X_h` the CQT computed with it:
+  ///
+  /// * The time of the energy within the frame is `Re(X_th / X_h)`, `X_th` being computed
+  ///   with the time-weighted kernel `(n - window_length / 2) * h[n]`.
+  /// * The instantaneous frequency is `-Im(X_dh / X_h)`, `X_dh` being computed with the
+  ///   derivative kernel `dh/dn`.
+  ///
+  /// The three kernels are folded into filterbanks for unwindowed frames, computed on the
+  /// first call and cached, so the frames are transformed with a single FFT each and
+  /// projected through the three filterbanks. The reassignment holds with the per-octave
+  /// windows and both phase conventions. The magnitudes reassigned out of the matrix, or to
+  /// a non-positive frequency, are dropped.
+  ///
+  /// # Arguments
+  ///
+  /// * `input_signal` - An Array1<f32> of the input audio signal
+  /// * `hop_size` - The number of samples to hop between frames
+  ///
+  /// # Returns
+  ///
+  /// * `Result<Array2<f32>, SignalError>` - The reassigned CQT feature matrix, of the same
+  ///   shape as the `process` one
+  pub fn process_reassigned(
+    &self,
+    signal: &[f32],
+    hop_size: usize
+  ) -> Result<Array2<f32>, SignalError> {
+    let window_len = self.cqt_params.window_length;
+
+    // Assign the input signal to the center of the padded signal, validating the hop size and signal
+    let signal_padded = pad_input_signal(
+      &self.pre_emphasized(signal),
+      window_len,
+      hop_size,
+      self.cqt_params.centering()
+    )?;
+    let num_frames = signal.len() / hop_size;
+
+    let [cqt_filtered, cqt_time, cqt_derivative] = self.compute_reassignment_frames(
+      num_frames,
+      |frame_idx| {
+        let start = frame_idx * hop_size;
+        let end = start + window_len;
+
+        signal_padded.slice(s![start..end])
+      }
+    )?;
+
+    let mut magnitude = magnitudes(&cqt_filtered);
+    self.scale_magnitudes(&mut magnitude);
+
+    let (num_frames, num_bins) = magnitude.dim();
+    let sample_rate = self.cqt_params.sample_rate as f32;
+    let bins_per_octave = self.cqt_params.bins_per_octave as f32;
+    let first_center_freq = self.cqt_params.center_freq(0);
+    let sign = self.cqt_params.phase_convention().sign();
+    let mut reassigned = Array2::<f32>::zeros((num_frames, num_bins));
+
+    for ((frame_idx, bin), &value) in magnitude.indexed_iter() {
+      if value == 0.0 {
+        continue;
+      }
+
+      let cqt_elem = cqt_filtered[[frame_idx, bin]];
+
+      // The filters sit at the negative frequencies with the negative convention
+      let angular_freq = -(cqt_derivative[[frame_idx, bin]] / cqt_elem).im;
+      let freq = (sign * angular_freq * sample_rate) / (2.0 * PI);
+      // The offset of the energy from the frame center in samples
+      let center_offset = (cqt_time[[frame_idx, bin]] / cqt_elem).re;
+
+      let target_frame = ((frame_idx as f32) + center_offset / (hop_size as f32)).round();
+      let target_bin = (bins_per_octave * (freq / first_center_freq).log2()).round();
+
+      // The negative targets and the NaN bins of the non-positive frequencies are dropped
+      if target_frame >= 0.0 && target_bin >= 0.0 {
+        let (target_frame, target_bin) = (target_frame as usize, target_bin as usize);
+
+        if target_frame < num_frames && target_bin < num_bins {
+          reassigned[[target_frame, target_bin]] += value;
+        }
+      }
+    }
+
+    Ok(reassigned)
+  }
+
+  /// Compute the complex CQT of `num_frames` frames of `window_length` samples with the
+  /// frame kernels, the time-weighted kernels and the derivative kernels of the reassignment.
+  ///
+  /// Each frame, or each octave of it with the per-octave windows, is transformed with the
+  /// FFT once, without the Hann window, and projected through the three filterbanks of
+  /// `reassignment_filterbanks`.
+  fn compute_reassignment_frames<'a, F>(
+    &self,
+    num_frames: usize,
+    frame_at: F
+  ) -> Result<[Array2<Complex<f32>>; 3], SignalError>
+    where F: Fn(usize) -> ArrayView1<'a, f32> + Sync
+  {
+    let remove_dc = self.cqt_params.remove_dc();
+    let reassignment_filterbanks = self.reassignment_filterbanks();
+
+    let Some(octave_filterbanks) = &self.octave_filterbanks else {
+      let fft_frames = compute_fft_frames(
+        num_frames,
+        &self.cqt_params.hann_window,
+        false,
+        remove_dc,
+        frame_at
+      )?;
+
+      return Ok(
+        reassignment_filterbanks[0].each_ref().map(|filterbank| {
+          project_frames(&fft_frames, filterbank)
+        })
+      );
+    };
+
+    let window_len = self.cqt_params.window_length;
+    let num_bins = self.filterbank.nrows();
+    let mut cqt_outputs: [Array2<Complex<f32>>; 3] = std::array::from_fn(|_| {
+      Array2::zeros((num_frames, num_bins))
+    });
+
+    for (octave, filterbanks) in octave_filterbanks.iter().zip(reassignment_filterbanks) {
+      // Center the octave window inside the full window so the frames stay aligned
+      let offset = (window_len - octave.window_length) / 2;
+      let end = offset + octave.window_length;
+      let last_bin = octave.first_bin + octave.filterbank.nrows();
+
+      let fft_frames = compute_fft_frames(
+        num_frames,
+        &octave.hann_window,
+        false,
+        remove_dc,
+        |frame_idx| frame_at(frame_idx).slice_move(s![offset..end])
+      )?;
+
+      for (cqt_output, filterbank) in cqt_outputs.iter_mut().zip(filterbanks) {
+        cqt_output
+          .slice_mut(s![.., octave.first_bin..last_bin])
+          .assign(&project_frames(&fft_frames, filterbank).mapv(|x| x * octave.gain));
+      }
+    }
+
+    Ok(cqt_outputs)
+  }
+
+  /// Return the filterbanks of the reassignment for unwindowed frames, one entry for the
+  /// filterbank or for each octave with the per-octave windows, computed on the first call
+  /// and cached.
+  fn reassignment_filterbanks(&self) -> &Vec<ReassignmentFilterbanks> {
+    self.reassignment_filterbanks.get_or_init(|| {
+      let Some(octave_filterbanks) = &self.octave_filterbanks else {
+        let hann_window = &self.cqt_params.hann_window;
+        return vec![compute_reassignment_filterbanks(hann_window, &self.filterbank)];
+      };
+
+      octave_filterbanks
+        .iter()
+        .map(|octave| compute_reassignment_filterbanks(&octave.hann_window, &octave.filterbank))
+        .collect()
+    })
+  }
+}
+
+/// Compute the filterbanks of the reassignment for unwindowed frames.
+///
+/// The frame kernel of a filter, the output of the filter for a unit impulse at each sample
+/// of a frame, is the forward FFT of its row. Multiplied by the Hann window, it gives the
+/// kernel `h` of the windowed transform, and `(n - window_length / 2) * h[n]` its time-weighted
+/// kernel. Both are folded back into filterbank rows with the inverse FFT, so projecting the
+/// FFT of an unwindowed frame through them applies the kernels. The derivative kernel `dh/dn`
+/// is the row of `h` multiplied by `-j * 2π * k / window_length`, `k` being the signed FFT bin.
+///
+/// # Arguments
+///
+/// * `hann_window` - The Hann window, its length is the length of the frames
+/// * `filterbank` - The filterbank of shape `(num_bins, window_length)`
+///
+/// # Returns
+///
+/// * `ReassignmentFilterbanks` - The kernel, time-weighted and derivative filterbanks
+fn compute_reassignment_filterbanks(
+  hann_window: &[f32],
+  filterbank: &Array2<Complex<f32>>
+) -> ReassignmentFilterbanks {
+  let window_len = hann_window.len();
+  let center = (window_len / 2) as f32;
+  // The inverse FFT isn't normalized
+  let scale = 1.0 / (window_len as f32);
+  let mut planner = FftPlanner::<f32>::new();
+  let fft = planner.plan_fft_forward(window_len);
+  let inverse_fft = planner.plan_fft_inverse(window_len);
+
+  let mut kernel_filterbank = Array2::<Complex<f32>>::zeros(filterbank.dim());
+  let mut time_filterbank = Array2::<Complex<f32>>::zeros(filterbank.dim());
+  let mut derivative_filterbank = Array2::<Complex<f32>>::zeros(filterbank.dim());
+
+  Zip::from(filterbank.rows())
+    .and(kernel_filterbank.rows_mut())
+    .and(time_filterbank.rows_mut())
+    .and(derivative_filterbank.rows_mut())
+    .par_for_each(|filter, mut kernel_row, mut time_row, mut derivative_row| {
+      // The kernel of the filter over the frame, windowed by the Hann window
+      let mut kernel = filter.to_vec();
+      fft.process(&mut kernel);
+      kernel
+        .iter_mut()
+        .zip(hann_window)
+        .for_each(|(kernel_elem, &window_elem)| {
+          *kernel_elem *= window_elem;
+        });
+      let mut time_kernel: Vec<Complex<f32>> = kernel
+        .iter()
+        .enumerate()
+        .map(|(n, &kernel_elem)| kernel_elem * ((n as f32) - center))
+        .collect();
+
+      inverse_fft.process(&mut kernel);
+      inverse_fft.process(&mut time_kernel);
+
+      for (k, (&kernel_elem, &time_elem)) in kernel.iter().zip(&time_kernel).enumerate() {
+        // The FFT bins above Nyquist are the negative frequencies
+        let signed_k = if k <= window_len / 2 { k as f32 } else { (k as f32) - (window_len as f32) };
+        let derivative = Complex::new(0.0, (-2.0 * PI * signed_k) / (window_len as f32));
+
+        kernel_row[k] = kernel_elem * scale;
+        time_row[k] = time_elem * scale;
+        derivative_row[k] = kernel_elem * scale * derivative;
+      }
+    });
+
+  [kernel_filterbank, time_filterbank, derivative_filterbank]
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{ CQTParams, PhaseConvention };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_process_reassigned() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // A steady tone between the centers of two bins, closer to the lower one
+    let bin = 60;
+    let freq = cqt.cqt_params.center_freq(bin) * (2.0f32).powf(0.3 / (BINS_PER_OCTAVE as f32));
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let magnitude = cqt.process(&signal, 2048).unwrap();
+    let reassigned = cqt.process_reassigned(&signal, 2048).unwrap();
+    assert_eq!(reassigned.dim(), magnitude.dim());
+
+    // The share of the magnitude in the bin of the tone
+    let bin_share = |matrix: &Array2<f32>| matrix.column(bin).sum() / matrix.sum();
+    assert!(bin_share(&reassigned) > 0.99);
+    assert!(bin_share(&reassigned) > bin_share(&magnitude));
+
+    // The magnitude is moved, not created
+    assert!(reassigned.sum() <= magnitude.sum() * (1.0 + 1e-4));
+
+    // The reassignment doesn't depend on the phase convention
+    let positive_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_phase_convention(PhaseConvention::Positive);
+    let positive_reassigned = Cqt::new(positive_params).process_reassigned(&signal, 2048).unwrap();
+    assert!(bin_share(&positive_reassigned) > 0.99);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use super::SignalError;
+
+/// Validates that a frame holds exactly `window_length` samples.
+///
+/// The frame-based entry points, such as `Cqt::process_single_frame`, don't pad their
+/// input, so every frame must be exactly one window long.
+///
+/// # Arguments
+///
+/// * `frame` - The frame as a slice of `f32` values.
+/// * `window_length` - The length of the analysis window.
+///
+/// # Returns
+///
+/// `Result<(), SignalError>`, `FrameLengthMismatch` if the frame length isn't `window_length`.
+pub fn validate_frame(frame: &[f32], window_length: usize) -> Result<(), SignalError> {
+  if frame.len() != window_length {
+    return Err(SignalError::FrameLengthMismatch);
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WINDOW_LENGTH: usize = 1024;
+
+  #[test]
+  fn test_validate_frame() {
+    assert_eq!(validate_frame(&[0.0; WINDOW_LENGTH], WINDOW_LENGTH), Ok(()));
+    assert_eq!(
+      validate_frame(&[0.0; WINDOW_LENGTH - 1], WINDOW_LENGTH),
+      Err(SignalError::FrameLengthMismatch)
+    );
+    assert_eq!(
+      validate_frame(&[0.0; WINDOW_LENGTH + 1], WINDOW_LENGTH),
+      Err(SignalError::FrameLengthMismatch)
+    );
+  }
+}
\ No newline at end of file
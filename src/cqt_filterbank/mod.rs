@@ -1,13 +1,23 @@
+mod octave_filterbank;
+
 use crate::{ create_complex_hann_window, CQTParams };
-use ndarray::{ Array2, Axis, parallel::prelude::* };
+use ndarray::{ Array1, Array2, ArrayViewMut1, Axis, parallel::prelude::* };
 use rustfft::{ FftPlanner, num_complex::Complex };
 use std::{ error::Error, fmt };
 
+pub use octave_filterbank::{
+  compute_octave_filterbanks,
+  compute_octave_filterbanks_with_planner,
+  OctaveFilterbank,
+};
+
 // Defining your custom error type
 #[derive(Debug)]
 pub enum CQTFilterbankError {
   InvalidParams,
   FFTError,
+  NonContiguous,
+  CustomWindowWithOctaveWindows,
 }
 
 // Implement the Error trait for the custom error type
@@ -19,6 +29,12 @@ impl fmt::Display for CQTFilterbankError {
     match self {
       CQTFilterbankError::InvalidParams => { write!(f, "Invalid parameters for CQT filterbank") }
       CQTFilterbankError::FFTError => { write!(f, "FFT error in CQT filterbank computation") }
+      CQTFilterbankError::NonContiguous => {
+        write!(f, "Non-contiguous window in CQT filterbank computation")
+      }
+      CQTFilterbankError::CustomWindowWithOctaveWindows => {
+        write!(f, "A custom window can't be used with the per-octave windows")
+      }
     }
   }
 }
@@ -41,40 +57,126 @@ impl fmt::Display for CQTFilterbankError {
 pub fn compute_cqt_filterbank(
   cqt_params: &CQTParams
 ) -> Result<Array2<Complex<f32>>, CQTFilterbankError> {
+  compute_cqt_filterbank_with_planner(cqt_params, &mut FftPlanner::new())
+}
+
+/// Computes a filterbank for the Constant-Q Transform (CQT), planning the FFT with
+/// the given planner.
+///
+/// Sharing a planner lets the FFT plans be reused across filterbanks and other transforms.
+///
+/// # Arguments
+///
+/// * `cqt_params` - CQTParams
+/// * `planner` - The FFT planner used to plan the forward FFT
+///
+/// # Returns
+///
+/// A 2D array of `Complex<f32>` values representing the filterbank.
+///
+/// # Errors
+///
+/// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank.
+pub fn compute_cqt_filterbank_with_planner(
+  cqt_params: &CQTParams,
+  planner: &mut FftPlanner<f32>
+) -> Result<Array2<Complex<f32>>, CQTFilterbankError> {
+  compute_filterbank_and_freqs(cqt_params, planner).map(|(filterbank, _)| filterbank)
+}
+
+/// Computes a filterbank for the Constant-Q Transform (CQT) along with the center
+/// frequency of each of its bins.
+///
+/// # Arguments
+///
+/// * `cqt_params` - CQTParams
+///
+/// # Returns
+///
+/// A tuple of the filterbank, as returned by `compute_cqt_filterbank`, and of the
+/// center frequencies used for each row of the filterbank.
+///
+/// # Errors
+///
+/// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank.
+pub fn compute_cqt_filterbank_with_freqs(
+  cqt_params: &CQTParams
+) -> Result<(Array2<Complex<f32>>, Array1<f32>), CQTFilterbankError> {
+  compute_filterbank_and_freqs(cqt_params, &mut FftPlanner::new())
+}
+
+/// Computes the CQT filterbank and the center frequencies of its bins, planning the FFT
+/// with the given planner.
+fn compute_filterbank_and_freqs(
+  cqt_params: &CQTParams,
+  planner: &mut FftPlanner<f32>
+) -> Result<(Array2<Complex<f32>>, Array1<f32>), CQTFilterbankError> {
   // Initialize a 2d Array to store the filterbank
   let mut filterbank = Array2::zeros((cqt_params.num_bins(), cqt_params.window_length));
+  // Compute the center frequency of each bin
+  let center_freqs = cqt_params.center_freqs();
 
-  // Initialize the FFT object
-  let fft = FftPlanner::new().plan_fft_forward(cqt_params.window_length);
+  // Plan the FFT with the given planner
+  let fft = planner.plan_fft_forward(cqt_params.window_length);
 
   filterbank
     .axis_iter_mut(Axis(0))
     .into_par_iter()
     .enumerate()
-    .for_each(|(bin, mut window)| {
-      // Compute the center frequency for this bin
-      let center_freq = cqt_params.center_freq(bin);
-
+    .try_for_each(|(bin, mut window)| {
       // Create a complex Hann window for this bin
-      let mut complex_hann_window = create_complex_hann_window(center_freq, &cqt_params);
+      let mut complex_hann_window = create_complex_hann_window(center_freqs[bin], cqt_params);
 
-      // Apply the FFT to the complex Hann window
+      // Apply the FFT to the complex Hann window. The window is an owned array in standard
+      // layout, so it is always contiguous and `NonContiguous` can't be returned here
       fft.process(
-        complex_hann_window
-          .as_slice_mut()
-          .expect("Error while getting slice of complex_hann_window")
+        complex_hann_window.as_slice_mut().ok_or(CQTFilterbankError::NonContiguous)?
       );
 
       // Assign the FFT result to the current window of the filterbank
       window.assign(&complex_hann_window);
-    });
+      apply_threshold(window, cqt_params.filterbank_threshold());
+
+      Ok(())
+    })?;
 
-  Ok(filterbank)
+  Ok((filterbank, center_freqs))
+}
+
+/// Zeroes the coefficients of a filter whose magnitude is below `threshold` times its
+/// largest magnitude.
+fn apply_threshold(mut filter: ArrayViewMut1<Complex<f32>>, threshold: f32) {
+  if threshold <= 0.0 {
+    return;
+  }
+
+  let cutoff = threshold * filter.fold(0.0f32, |max, x| max.max(x.norm()));
+
+  filter.map_inplace(|x| {
+    if x.norm() < cutoff {
+      *x = Complex::new(0.0, 0.0);
+    }
+  });
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::{ CQTParams, compute_cqt_filterbank };
+  use std::f32::consts::PI;
+
+  use approx::assert_relative_eq;
+  use ndarray::Array2;
+  use rustfft::{ FftPlanner, num_complex::Complex };
+
+  use crate::{
+    CQTFilterbankError,
+    CQTParams,
+    Cqt,
+    WindowType,
+    compute_cqt_filterbank,
+    compute_cqt_filterbank_with_freqs,
+    compute_cqt_filterbank_with_planner,
+    compute_octave_filterbanks,
+  };
 
   const MIN_FREQ: f32 = 20.0;
   const MAX_FREQ: f32 = 7902.1;
@@ -96,6 +198,78 @@ mod tests {
     assert!(filterbank.is_ok());
   }
 
+  #[test]
+  fn test_compute_cqt_filterbank_options_ok() {
+    let cqt_params = || {
+      CQTParams::new(MIN_FREQ, MAX_FREQ, BINS_PER_OCTAVE, SAMPLE_RATE, WINDOW_LENGTH).unwrap()
+    };
+    let all_params = [
+      cqt_params().with_edge_taper(true),
+      cqt_params().with_per_bin_norm(true),
+      cqt_params().with_window_type(WindowType::Kaiser { beta: 8.0 }).unwrap(),
+      cqt_params().with_octave_windows(true),
+    ];
+
+    for cqt_params in &all_params {
+      assert!(compute_cqt_filterbank(cqt_params).is_ok());
+      assert!(compute_octave_filterbanks(cqt_params).is_ok());
+    }
+  }
+
+  #[test]
+  fn test_compute_cqt_filterbank_threshold() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let threshold_cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_filterbank_threshold(1e-4);
+
+    let filterbank = compute_cqt_filterbank(&cqt_params).unwrap();
+    let threshold_filterbank = compute_cqt_filterbank(&threshold_cqt_params).unwrap();
+    let num_zeros = |filterbank: &Array2<Complex<f32>>| {
+      filterbank
+        .iter()
+        .filter(|x| x.re == 0.0 && x.im == 0.0)
+        .count()
+    };
+
+    // Most of the coefficients of the narrow filters are negligible
+    assert!(num_zeros(&filterbank) < filterbank.len() / 2);
+    assert!(num_zeros(&threshold_filterbank) > filterbank.len() / 2);
+
+    // The 440 Hz peak is unchanged
+    let cqt = Cqt::new(cqt_params);
+    let threshold_cqt = Cqt::new(threshold_cqt_params);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * 440.0 * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let result = cqt.process(&signal, 2048).unwrap();
+    let threshold_result = threshold_cqt.process(&signal, 2048).unwrap();
+    let frame = result.nrows() / 2;
+    let peak_bin = result
+      .row(frame)
+      .iter()
+      .enumerate()
+      .fold(0, |peak, (bin, &value)| if value > result[[frame, peak]] { bin } else { peak });
+    assert_relative_eq!(
+      threshold_result[[frame, peak_bin]],
+      result[[frame, peak_bin]],
+      max_relative = 1e-3
+    );
+  }
+
   #[test]
   fn test_compute_cqt_filterbank_dimensions() {
     let cqt_params = CQTParams::new(
@@ -110,4 +284,39 @@ mod tests {
     let num_bins = ((BINS_PER_OCTAVE as f32) * (MAX_FREQ / MIN_FREQ).log2().ceil()) as usize;
     assert_eq!(filterbank.dim(), (num_bins, WINDOW_LENGTH));
   }
+
+  #[test]
+  fn test_compute_cqt_filterbank_with_planner() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let mut planner = FftPlanner::new();
+
+    let filterbank = compute_cqt_filterbank(&cqt_params).unwrap();
+    let planner_filterbank = compute_cqt_filterbank_with_planner(&cqt_params, &mut planner);
+
+    assert_eq!(planner_filterbank.unwrap(), filterbank);
+  }
+
+  #[test]
+  fn test_compute_cqt_filterbank_with_freqs() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .unwrap()
+      .with_tuning_cents(-25.0);
+
+    let (filterbank, center_freqs) = compute_cqt_filterbank_with_freqs(&cqt_params).unwrap();
+
+    assert_eq!(filterbank, compute_cqt_filterbank(&cqt_params).unwrap());
+    assert_eq!(center_freqs, cqt_params.center_freqs());
+  }
 }
\ No newline at end of file
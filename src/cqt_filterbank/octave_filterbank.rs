@@ -0,0 +1,223 @@
+use ndarray::{ Array2, Axis, parallel::prelude::* };
+use rustfft::{ FftPlanner, num_complex::Complex };
+
+use crate::{ create_complex_hann_window, CQTParams, FilterNorm };
+
+use super::{ apply_threshold, CQTFilterbankError };
+
+/// The filterbank of a single octave analyzed with its own window length.
+#[derive(Debug)]
+pub struct OctaveFilterbank {
+  /// The index of the first bin of the octave in the full filterbank.
+  pub first_bin: usize,
+  /// The window length used for this octave.
+  pub window_length: usize,
+  /// The Hann window of the octave.
+  pub hann_window: Vec<f32>,
+  /// The filterbank of the octave, of shape `(bins_in_octave, window_length)`.
+  pub filterbank: Array2<Complex<f32>>,
+  /// The gain matching the octave output to the full window output.
+  pub gain: f32,
+}
+
+/// Computes a filterbank for each octave of the Constant-Q Transform (CQT),
+/// each octave using its own window length.
+///
+/// # Arguments
+///
+/// * `cqt_params` - CQTParams
+///
+/// # Returns
+///
+/// A vector of `OctaveFilterbank`, ordered from the lowest to the highest octave.
+///
+/// # Errors
+///
+/// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank,
+/// or `CustomWindowWithOctaveWindows` if the parameters hold a custom window.
+pub fn compute_octave_filterbanks(
+  cqt_params: &CQTParams
+) -> Result<Vec<OctaveFilterbank>, CQTFilterbankError> {
+  compute_octave_filterbanks_with_planner(cqt_params, &mut FftPlanner::new())
+}
+
+/// Computes a filterbank for each octave of the Constant-Q Transform (CQT), planning
+/// the FFTs with the given planner.
+///
+/// # Arguments
+///
+/// * `cqt_params` - CQTParams
+/// * `planner` - The FFT planner used to plan the forward FFTs
+///
+/// # Returns
+///
+/// A vector of `OctaveFilterbank`, ordered from the lowest to the highest octave.
+///
+/// # Errors
+///
+/// Returns a `CQTFilterbankError` if there was an error while creating the CQT filterbank,
+/// or `CustomWindowWithOctaveWindows` if the parameters hold a custom window.
+pub fn compute_octave_filterbanks_with_planner(
+  cqt_params: &CQTParams,
+  planner: &mut FftPlanner<f32>
+) -> Result<Vec<OctaveFilterbank>, CQTFilterbankError> {
+  // A custom window has a single length, so it can't be used for the shorter octaves
+  if cqt_params.custom_window() {
+    return Err(CQTFilterbankError::CustomWindowWithOctaveWindows);
+  }
+
+  let num_bins = cqt_params.num_bins();
+  let bins_per_octave = cqt_params.bins_per_octave;
+
+  (0..num_bins)
+    .step_by(bins_per_octave)
+    .map(|first_bin| {
+      let last_bin = (first_bin + bins_per_octave).min(num_bins);
+      let window_length = cqt_params.octave_window_length(first_bin);
+
+      // Parameters of the octave, sharing everything but the window length
+      let octave_params = cqt_params.for_octave_window_length(window_length);
+
+      // Initialize a 2d Array to store the filterbank of the octave
+      let mut filterbank = Array2::zeros((last_bin - first_bin, window_length));
+
+      // Plan the FFT with the given planner
+      let fft = planner.plan_fft_forward(window_length);
+
+      filterbank
+        .axis_iter_mut(Axis(0))
+        .into_par_iter()
+        .enumerate()
+        .try_for_each(|(octave_bin, mut window)| {
+          // Compute the center frequency for this bin
+          let center_freq = cqt_params.center_freq(first_bin + octave_bin);
+
+          // Create a complex Hann window for this bin
+          let mut complex_hann_window = create_complex_hann_window(center_freq, &octave_params);
+
+          // Apply the FFT to the complex Hann window. The window is an owned array in standard
+          // layout, so it is always contiguous and `NonContiguous` can't be returned here
+          fft.process(
+            complex_hann_window.as_slice_mut().ok_or(CQTFilterbankError::NonContiguous)?
+          );
+
+          // Assign the FFT result to the current window of the filterbank
+          window.assign(&complex_hann_window);
+          apply_threshold(window, octave_params.filterbank_threshold());
+
+          Ok(())
+        })?;
+
+      // Both the frame and the window spectra scale with the window length, except for
+      // the unity peak normalization which already accounts for it
+      let gain = match cqt_params.filter_norm() {
+        FilterNorm::UnityPeak => 1.0,
+        _ => ((cqt_params.window_length as f32) / (window_length as f32)).powi(2),
+      };
+
+      Ok(OctaveFilterbank {
+        first_bin,
+        window_length,
+        hann_window: octave_params.hann_window,
+        filterbank,
+        gain,
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{ CQTParams, WindowType };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_compute_octave_filterbanks() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+
+    let octave_filterbanks = compute_octave_filterbanks(&cqt_params).unwrap();
+    let num_bins: usize = octave_filterbanks
+      .iter()
+      .map(|octave| octave.filterbank.nrows())
+      .sum();
+
+    assert_eq!(octave_filterbanks.len(), 9);
+    assert_eq!(num_bins, cqt_params.num_bins());
+
+    // The window length never grows from one octave to the next
+    for (octave, next_octave) in octave_filterbanks.iter().zip(octave_filterbanks.iter().skip(1)) {
+      assert!(next_octave.window_length <= octave.window_length);
+      assert_eq!(next_octave.first_bin, octave.first_bin + BINS_PER_OCTAVE);
+    }
+    assert_eq!(octave_filterbanks[0].window_length, WINDOW_LENGTH);
+    assert!(octave_filterbanks[8].window_length < WINDOW_LENGTH);
+  }
+
+  #[test]
+  fn test_compute_octave_filterbanks_single_bin_octave() {
+    // The highest octave holds a single bin, the highest bin of the filterbank
+    let cqt_params = CQTParams::with_n_bins(
+      MIN_FREQ,
+      BINS_PER_OCTAVE,
+      BINS_PER_OCTAVE + 1,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let octave_filterbanks = compute_octave_filterbanks(&cqt_params).unwrap();
+    assert_eq!(octave_filterbanks.len(), 2);
+    assert_eq!(octave_filterbanks[1].filterbank.nrows(), 1);
+
+    let cqt_params = CQTParams::with_n_bins(MIN_FREQ, BINS_PER_OCTAVE, 1, SAMPLE_RATE, 256).unwrap();
+    let octave_filterbanks = compute_octave_filterbanks(&cqt_params).unwrap();
+    assert_eq!(octave_filterbanks.len(), 1);
+    assert_eq!(octave_filterbanks[0].filterbank.dim(), (1, 256));
+  }
+
+  #[test]
+  fn test_compute_octave_filterbanks_window() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    )
+      .and_then(|cqt_params| cqt_params.with_window_type(WindowType::Kaiser { beta: 8.0 }))
+      .unwrap();
+
+    // The octaves use the window type with their own length
+    for octave in compute_octave_filterbanks(&cqt_params).unwrap() {
+      assert_eq!(
+        octave.hann_window,
+        WindowType::Kaiser { beta: 8.0 }.create_window(octave.window_length)
+      );
+    }
+
+    // A custom window has a single length
+    let window = vec![1.0; WINDOW_LENGTH];
+    let cqt_params = CQTParams::with_window(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      window
+    ).unwrap();
+    assert!(matches!(
+      compute_octave_filterbanks(&cqt_params),
+      Err(CQTFilterbankError::CustomWindowWithOctaveWindows)
+    ));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use ndarray::{ Array2, Zip };
+
+/// Checks whether two CQT matrices are equal within a tolerance.
+///
+/// # Arguments
+///
+/// * `a` - The first matrix.
+/// * `b` - The second matrix.
+/// * `epsilon` - The largest absolute difference allowed between two elements.
+///
+/// # Returns
+///
+/// * `bool` - Whether the matrices have the same shape and every pair of elements
+///   differs by at most `epsilon`. Any `NaN` makes the matrices unequal.
+pub fn approx_equal(a: &Array2<f32>, b: &Array2<f32>, epsilon: f32) -> bool {
+  a.dim() == b.dim() &&
+    Zip::from(a)
+      .and(b)
+      .all(|&a_elem, &b_elem| (a_elem - b_elem).abs() <= epsilon)
+}
+
+#[cfg(test)]
+mod tests {
+  use ndarray::array;
+
+  use super::*;
+
+  #[test]
+  fn test_approx_equal() {
+    let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+    assert!(approx_equal(&matrix, &matrix.clone(), 0.0));
+
+    let mut perturbed = matrix.clone();
+    perturbed[[1, 0]] += 1e-3;
+    assert!(approx_equal(&matrix, &perturbed, 1e-2));
+    assert!(!approx_equal(&matrix, &perturbed, 1e-4));
+
+    assert!(!approx_equal(&matrix, &array![[1.0, 2.0]], 1.0));
+
+    perturbed[[0, 0]] = f32::NAN;
+    assert!(!approx_equal(&matrix, &perturbed, 1.0));
+  }
+}
\ No newline at end of file
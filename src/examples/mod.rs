@@ -1,3 +1,5 @@
+mod approx_equal;
 mod dummy_audio_signal;
 
+pub use approx_equal::approx_equal;
 pub use dummy_audio_signal::create_dummy_audio_signal;
\ No newline at end of file
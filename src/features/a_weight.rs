@@ -0,0 +1,107 @@
+use ndarray::{ Array2, Axis };
+
+use crate::CQTParams;
+
+use super::FeatureError;
+
+/// Applies the A-weighting to the bins of a CQT magnitude matrix.
+///
+/// Each bin is multiplied by the IEC 61672 A-weighting gain evaluated at its center
+/// frequency, normalized to a unit gain at 1 kHz.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `cqt_params` - The CQTParams used to compute the matrix.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The weighted matrix of shape `(num_frames, num_bins)`.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if the number of columns of the matrix isn't the number of bins.
+pub fn a_weight(matrix: &Array2<f32>, cqt_params: &CQTParams) -> Result<Array2<f32>, FeatureError> {
+  if matrix.ncols() != cqt_params.num_bins() {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let mut weighted = matrix.clone();
+
+  for (bin, mut column) in weighted.axis_iter_mut(Axis(1)).enumerate() {
+    column *= a_weighting_gain(cqt_params.center_freq(bin));
+  }
+
+  Ok(weighted)
+}
+
+/// Computes the linear A-weighting gain at a frequency, the gain at 1 kHz being 1.
+///
+/// # Arguments
+///
+/// * `freq` - The frequency in Hz.
+fn a_weighting_gain(freq: f32) -> f32 {
+  // Offset of +2.00 dB normalizing the response to 0 dB at 1 kHz
+  const GAIN_1KHZ: f64 = 1.258_925_4;
+
+  let f2 = f64::from(freq).powi(2);
+  let response =
+    (12194.0_f64.powi(2) * f2.powi(2)) /
+    ((f2 + 20.6_f64.powi(2)) *
+      ((f2 + 107.7_f64.powi(2)) * (f2 + 737.9_f64.powi(2))).sqrt() *
+      (f2 + 12194.0_f64.powi(2)));
+
+  (response * GAIN_1KHZ) as f32
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_abs_diff_eq;
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 31.25;
+  const MAX_FREQ: f32 = 16000.0;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_a_weight() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let matrix = Array2::<f32>::ones((2, cqt_params.num_bins()));
+
+    let weighted = a_weight(&matrix, &cqt_params).unwrap();
+    let gain_db = |bin: usize| 20.0 * weighted[[0, bin]].log10();
+
+    // 1 kHz, 5 octaves above the minimum frequency
+    let bin_1khz = 5 * BINS_PER_OCTAVE;
+    assert_abs_diff_eq!(cqt_params.center_freq(bin_1khz), 1000.0, epsilon = 1e-2);
+    assert_abs_diff_eq!(gain_db(bin_1khz), 0.0, epsilon = 0.01);
+
+    // The tabulated A-weighting is about -39.5 dB at 31.5 Hz, a bit lower at 31.25 Hz,
+    // and -1.1 dB at 8 kHz
+    assert!(gain_db(0) < -39.5 && gain_db(0) > -40.0);
+    assert_abs_diff_eq!(gain_db(8 * BINS_PER_OCTAVE), -1.1, epsilon = 0.1);
+  }
+
+  #[test]
+  fn test_a_weight_bin_count_mismatch() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let matrix = Array2::<f32>::zeros((2, cqt_params.num_bins() + 1));
+
+    assert_eq!(a_weight(&matrix, &cqt_params), Err(FeatureError::BinCountMismatch));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,88 @@
+use ndarray::{ Array2, ArrayView1, Axis };
+
+use super::FeatureError;
+
+/// Finds the frame of a CQT magnitude matrix best matching a template spectrum.
+///
+/// The frames are compared to the template with the cosine similarity,
+/// `frame · template / (|frame| * |template|)`. The similarity of a silent frame, or of
+/// any frame with a silent template, is 0.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `template` - The template spectrum of `num_bins` values.
+///
+/// # Returns
+///
+/// * `Result<(usize, f32), FeatureError>` - The index of the best matching frame and its
+///   cosine similarity. Returns `(0, 0.0)` for a matrix without frames.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if the length of the template isn't the number of columns
+/// of the matrix.
+pub fn best_matching_frame(
+  matrix: &Array2<f32>,
+  template: ArrayView1<f32>
+) -> Result<(usize, f32), FeatureError> {
+  if template.len() != matrix.ncols() {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let template_norm = template.dot(&template).sqrt();
+  let mut best = (0, 0.0);
+
+  for (frame_idx, frame) in matrix.axis_iter(Axis(0)).enumerate() {
+    let norm = frame.dot(&frame).sqrt() * template_norm;
+    let similarity = if norm > 0.0 { frame.dot(&template) / norm } else { 0.0 };
+
+    if frame_idx == 0 || similarity > best.1 {
+      best = (frame_idx, similarity);
+    }
+  }
+
+  Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_relative_eq;
+  use ndarray::{ array, Array1 };
+
+  use super::*;
+
+  #[test]
+  fn test_best_matching_frame() {
+    let mut matrix = Array2::from_shape_fn((20, 12), |(frame, bin)| {
+      ((frame as f32) * 0.7 + (bin as f32) * 1.3).sin().abs()
+    });
+    let template = Array1::from_shape_fn(12, |bin| if bin % 4 == 0 { 1.0 } else { 0.1 });
+
+    // Embed a scaled copy of the template as frame 13
+    matrix.row_mut(13).assign(&(&template * 3.0));
+
+    let (frame_idx, similarity) = best_matching_frame(&matrix, template.view()).unwrap();
+    assert_eq!(frame_idx, 13);
+    assert_relative_eq!(similarity, 1.0, max_relative = 1e-6);
+  }
+
+  #[test]
+  fn test_best_matching_frame_silent() {
+    let matrix = Array2::<f32>::zeros((3, 4));
+    let template = array![1.0, 0.0, 0.0, 0.0];
+
+    assert_eq!(best_matching_frame(&matrix, template.view()), Ok((0, 0.0)));
+  }
+
+  #[test]
+  fn test_best_matching_frame_bin_count_mismatch() {
+    let matrix = Array2::<f32>::zeros((3, 4));
+    let template = array![1.0, 0.0, 0.0];
+
+    assert_eq!(
+      best_matching_frame(&matrix, template.view()),
+      Err(FeatureError::BinCountMismatch)
+    );
+  }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use ndarray::{ Array2, Axis, s };
+
+use super::FeatureError;
+
+/// Finds the time offset best aligning two CQT magnitude matrices.
+///
+/// The frames are compared with the normalized cross-correlation summed across bins,
+/// the missing frames at each lag being treated as zeros. A positive lag means `b` is
+/// delayed relative to `a`, frame `t` of `b` matching frame `t - lag` of `a`.
+///
+/// # Arguments
+///
+/// * `a` - The reference matrix of shape `(num_frames_a, num_bins)`.
+/// * `b` - The matrix to align of shape `(num_frames_b, num_bins)`.
+///
+/// # Returns
+///
+/// * `Result<(isize, f32), FeatureError>` - The lag in frames and its correlation score,
+///   normalized by the norms of both matrices. Returns `(0, 0.0)` if either matrix is
+///   silent or empty.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if the matrices don't have the same number of bins.
+pub fn best_offset(a: &Array2<f32>, b: &Array2<f32>) -> Result<(isize, f32), FeatureError> {
+  if a.ncols() != b.ncols() {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let norm = (a.iter().map(|x| x * x).sum::<f32>() * b.iter().map(|x| x * x).sum::<f32>()).sqrt();
+
+  if norm == 0.0 {
+    return Ok((0, 0.0));
+  }
+
+  let num_frames_a = a.nrows() as isize;
+  let num_frames_b = b.nrows() as isize;
+  let mut best = (0, f32::NEG_INFINITY);
+
+  for lag in 1 - num_frames_a..num_frames_b {
+    // The frames of `b` overlapping `a` at this lag
+    let start = lag.max(0);
+    let end = num_frames_b.min(num_frames_a + lag);
+
+    let overlap_a = a.slice(s![start - lag..end - lag, ..]);
+    let overlap_b = b.slice(s![start..end, ..]);
+    let score = (&overlap_a * &overlap_b).sum_axis(Axis(1)).sum() / norm;
+
+    if score > best.1 {
+      best = (lag, score);
+    }
+  }
+
+  Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_relative_eq;
+
+  use super::*;
+
+  fn test_matrix(num_frames: usize) -> Array2<f32> {
+    Array2::from_shape_fn((num_frames, 12), |(frame, bin)| {
+      ((frame as f32) * 0.7 + (bin as f32) * 1.3).sin().abs()
+    })
+  }
+
+  #[test]
+  fn test_best_offset_delayed() {
+    let a = test_matrix(40);
+    let shift = 5;
+
+    // `b` is `a` delayed by `shift` frames
+    let mut b = Array2::<f32>::zeros((40 + shift, 12));
+    b.slice_mut(s![shift.., ..]).assign(&a);
+
+    let (lag, score) = best_offset(&a, &b).unwrap();
+    assert_eq!(lag, shift as isize);
+    assert_relative_eq!(score, 1.0, max_relative = 1e-5);
+  }
+
+  #[test]
+  fn test_best_offset_advanced() {
+    let a = test_matrix(40);
+
+    // `b` starts 3 frames into `a` and is shorter
+    let b = a.slice(s![3..30, ..]).to_owned();
+
+    let (lag, score) = best_offset(&a, &b).unwrap();
+    assert_eq!(lag, -3);
+    assert!(score > 0.0 && score < 1.0);
+  }
+
+  #[test]
+  fn test_best_offset_silent() {
+    let a = test_matrix(10);
+    let b = Array2::<f32>::zeros((10, 12));
+
+    assert_eq!(best_offset(&a, &b), Ok((0, 0.0)));
+  }
+
+  #[test]
+  fn test_best_offset_bin_count_mismatch() {
+    let a = test_matrix(10);
+    let b = Array2::<f32>::zeros((10, 24));
+
+    assert_eq!(best_offset(&a, &b), Err(FeatureError::BinCountMismatch));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+use ndarray::{ Array2, ArrayView1, ArrayViewMut1, Axis };
+
+use crate::CQTParams;
+
+use super::FeatureError;
+
+/// The number of pitch classes of a chromagram.
+pub const NUM_PITCH_CLASSES: usize = 12;
+
+/// Folds a CQT magnitude matrix into a chromagram of 12 pitch classes.
+///
+/// Each bin is summed into the pitch class of its nearest semitone, the pitch class
+/// of `min_freq` being the first one.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `cqt_params` - The CQTParams used to compute the matrix.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The chromagram of shape `(num_frames, 12)`.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if the number of columns of the matrix isn't the number of bins.
+pub fn chroma(matrix: &Array2<f32>, cqt_params: &CQTParams) -> Result<Array2<f32>, FeatureError> {
+  if matrix.ncols() != cqt_params.num_bins() {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let mut chromagram = Array2::<f32>::zeros((matrix.nrows(), NUM_PITCH_CLASSES));
+
+  for (frame, chroma_row) in matrix.axis_iter(Axis(0)).zip(chromagram.axis_iter_mut(Axis(0))) {
+    fold_to_chroma(frame, cqt_params.bins_per_octave, chroma_row);
+  }
+
+  Ok(chromagram)
+}
+
+/// Sums the bins of a CQT frame into its 12 pitch classes.
+///
+/// # Arguments
+///
+/// * `frame` - The CQT magnitudes of the frame.
+/// * `bins_per_octave` - The number of bins per octave of the frame.
+/// * `chroma_row` - The 12 pitch classes the bins are added to.
+pub(crate) fn fold_to_chroma(
+  frame: ArrayView1<f32>,
+  bins_per_octave: usize,
+  mut chroma_row: ArrayViewMut1<f32>
+) {
+  for (bin, &value) in frame.iter().enumerate() {
+    chroma_row[pitch_class(bin, bins_per_octave)] += value;
+  }
+}
+
+/// Returns the pitch class of the nearest semitone of a bin, the pitch class
+/// of `min_freq` being the first one.
+///
+/// # Arguments
+///
+/// * `bin` - The index of the bin.
+/// * `bins_per_octave` - The number of bins per octave.
+pub(crate) fn pitch_class(bin: usize, bins_per_octave: usize) -> usize {
+  // The nearest semitone of the bin
+  let semitone = (((NUM_PITCH_CLASSES * bin) as f32) / (bins_per_octave as f32)).round();
+
+  (semitone as usize) % NUM_PITCH_CLASSES
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_relative_eq;
+
+  use crate::{ create_dummy_audio_signal, Cqt };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 27.5; // A0
+  const MAX_FREQ: f32 = 4186.0; // C8
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_chroma() {
+    for bins_per_octave in [12, 36] {
+      let cqt_params = CQTParams::new(
+        MIN_FREQ,
+        MAX_FREQ,
+        bins_per_octave,
+        SAMPLE_RATE,
+        WINDOW_LENGTH
+      ).unwrap();
+      let cqt = Cqt::new(cqt_params);
+      let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+      let matrix = cqt.process(&signal, 2048).unwrap();
+
+      let chromagram = chroma(&matrix, cqt.cqt_params()).unwrap();
+      assert_eq!(chromagram.dim(), (matrix.nrows(), NUM_PITCH_CLASSES));
+      assert_relative_eq!(chromagram.sum(), matrix.sum(), max_relative = 1e-5);
+
+      // A4 has the pitch class of A0
+      let frame = chromagram.row(chromagram.nrows() / 2);
+      let peak = frame
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > frame[peak] { idx } else { peak });
+      assert_eq!(peak, 0);
+    }
+  }
+
+  #[test]
+  fn test_chroma_bin_count_mismatch() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, 12, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let matrix = Array2::<f32>::zeros((2, cqt_params.num_bins() + 1));
+
+    let result = chroma(&matrix, &cqt_params);
+    assert_eq!(result, Err(FeatureError::BinCountMismatch));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use ndarray::Array2;
+
+/// Clamps the magnitudes of a CQT matrix element-wise to the range `[0, max]`.
+///
+/// Values above `max` saturate to exactly `max` and negative values become 0, every value in
+/// between is returned unchanged. NaN values are kept as NaN.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `max` - The upper bound of the output range.
+///
+/// # Returns
+///
+/// * `Array2<f32>` - The clamped matrix, of the same shape as the input.
+///
+/// # Panics
+///
+/// Panics if `max` is negative or NaN.
+pub fn clamp_magnitudes(matrix: &Array2<f32>, max: f32) -> Array2<f32> {
+  assert!(max >= 0.0, "The maximum magnitude must not be negative");
+
+  matrix.mapv(|value| value.clamp(0.0, max))
+}
+
+#[cfg(test)]
+mod tests {
+  use ndarray::array;
+
+  use super::*;
+
+  #[test]
+  fn test_clamp_magnitudes() {
+    let matrix = array![[0.0, 0.25, 0.5], [1.0, 2.0, 100.0]];
+
+    let clamped = clamp_magnitudes(&matrix, 1.0);
+
+    assert_eq!(clamped, array![[0.0, 0.25, 0.5], [1.0, 1.0, 1.0]]);
+  }
+
+  #[test]
+  fn test_clamp_magnitudes_negative() {
+    let matrix = array![[-1.0, 0.5]];
+
+    assert_eq!(clamp_magnitudes(&matrix, 1.0), array![[0.0, 0.5]]);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use ndarray::Array2;
+
+use super::CompressMode;
+
+/// Compresses the magnitudes of a CQT matrix element-wise.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `mode` - The compression applied to each magnitude.
+///
+/// # Returns
+///
+/// * `Array2<f32>` - The compressed matrix, of the same shape as the input.
+pub fn compress(matrix: &Array2<f32>, mode: CompressMode) -> Array2<f32> {
+  matrix.mapv(|value| mode.apply(value))
+}
+
+#[cfg(test)]
+mod tests {
+  use ndarray::array;
+
+  use super::*;
+
+  const MODES: [CompressMode; 2] = [
+    CompressMode::Log1p { gamma: 100.0 },
+    CompressMode::MuLaw { mu: 255.0 },
+  ];
+
+  #[test]
+  fn test_compress_zero() {
+    let matrix = Array2::<f32>::zeros((2, 3));
+
+    for mode in MODES {
+      assert_eq!(compress(&matrix, mode), matrix);
+    }
+  }
+
+  #[test]
+  fn test_compress_is_monotonic() {
+    let matrix = array![[0.0, 0.001, 0.01, 0.1, 0.5, 1.0, 10.0]];
+
+    for mode in MODES {
+      let compressed = compress(&matrix, mode);
+      assert!(compressed.windows((1, 2)).into_iter().all(|pair| pair[[0, 0]] < pair[[0, 1]]));
+    }
+  }
+
+  #[test]
+  fn test_compress_reduces_dynamic_range() {
+    let matrix = array![[0.0, 0.001], [0.5, 1.0]];
+    let dynamic_range = |matrix: &Array2<f32>| matrix[[1, 1]] / matrix[[0, 1]];
+
+    for mode in MODES {
+      let compressed = compress(&matrix, mode);
+      assert!(dynamic_range(&compressed) < dynamic_range(&matrix));
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+/// The compression applied to the CQT magnitudes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompressMode {
+  /// `log(1 + gamma * x)`
+  Log1p {
+    gamma: f32,
+  },
+  /// `log(1 + mu * x) / log(1 + mu)`, mapping `[0, 1]` onto `[0, 1]`
+  MuLaw {
+    mu: f32,
+  },
+}
+
+impl CompressMode {
+  /// Compress a single value.
+  ///
+  /// The sign of the value is kept, so the compression is monotonic and maps zero to zero
+  /// for a positive `gamma` or `mu`.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The value to compress.
+  ///
+  /// # Returns
+  ///
+  /// * `f32` - The compressed value.
+  pub fn apply(&self, value: f32) -> f32 {
+    let compressed = match self {
+      CompressMode::Log1p { gamma } => (gamma * value.abs()).ln_1p(),
+      CompressMode::MuLaw { mu } => (mu * value.abs()).ln_1p() / mu.ln_1p(),
+    };
+
+    compressed.copysign(value)
+  }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use ndarray::{ Array1, Zip };
+
+/// Running per-bin statistics over the frames of a CQT magnitude matrix.
+///
+/// The mean and variance are accumulated with Welford's algorithm, one frame at a time,
+/// so the frames never need to be held in memory at once.
+#[derive(Debug, Clone)]
+pub struct CqtStats {
+  num_frames: usize,
+  mean: Array1<f32>,
+  squared_deviations: Array1<f32>,
+}
+
+impl CqtStats {
+  /// Creates an empty accumulator.
+  ///
+  /// # Arguments
+  ///
+  /// * `num_bins` - The number of frequency bins of the frames.
+  pub fn new(num_bins: usize) -> Self {
+    Self {
+      num_frames: 0,
+      mean: Array1::zeros(num_bins),
+      squared_deviations: Array1::zeros(num_bins),
+    }
+  }
+
+  /// Accumulates a frame.
+  ///
+  /// # Arguments
+  ///
+  /// * `frame` - The CQT frame, holding one value per frequency bin.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the frame length doesn't match the number of bins of the accumulator.
+  pub fn update(&mut self, frame: &Array1<f32>) {
+    self.num_frames += 1;
+    let num_frames = self.num_frames as f32;
+
+    Zip::from(&mut self.mean)
+      .and(&mut self.squared_deviations)
+      .and(frame)
+      .for_each(|mean, squared_deviation, &value| {
+        let delta = value - *mean;
+        *mean += delta / num_frames;
+        *squared_deviation += delta * (value - *mean);
+      });
+  }
+
+  /// Returns the number of frames accumulated so far.
+  pub fn num_frames(&self) -> usize {
+    self.num_frames
+  }
+
+  /// Consumes the accumulator, returning the per-bin statistics.
+  ///
+  /// # Returns
+  ///
+  /// * `(Array1<f32>, Array1<f32>)` - The per-bin mean and population variance,
+  ///   both zero when no frame was accumulated.
+  pub fn finalize(self) -> (Array1<f32>, Array1<f32>) {
+    let variance = if self.num_frames > 0 {
+      self.squared_deviations / (self.num_frames as f32)
+    } else {
+      self.squared_deviations
+    };
+
+    (self.mean, variance)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ndarray::{ Array2, Axis };
+
+  use super::*;
+
+  #[test]
+  fn test_cqt_stats() {
+    let matrix = Array2::from_shape_fn((257, 12), |(frame, bin)| {
+      ((frame as f32) * 0.37 + (bin as f32)).sin().abs() * ((bin + 1) as f32)
+    });
+
+    let mut stats = CqtStats::new(matrix.ncols());
+    for frame in matrix.rows() {
+      stats.update(&frame.to_owned());
+    }
+    assert_eq!(stats.num_frames(), 257);
+
+    let (mean, variance) = stats.finalize();
+    let expected_mean = matrix.mean_axis(Axis(0)).unwrap();
+    let expected_variance = matrix.var_axis(Axis(0), 0.0);
+
+    for (value, expected) in mean.iter().zip(expected_mean.iter()) {
+      assert!((value - expected).abs() < 1e-4);
+    }
+    for (value, expected) in variance.iter().zip(expected_variance.iter()) {
+      assert!((value - expected).abs() < 1e-3);
+    }
+  }
+
+  #[test]
+  fn test_cqt_stats_empty() {
+    let (mean, variance) = CqtStats::new(4).finalize();
+
+    assert_eq!(mean, Array1::<f32>::zeros(4));
+    assert_eq!(variance, Array1::<f32>::zeros(4));
+  }
+}
\ No newline at end of file
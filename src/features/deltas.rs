@@ -0,0 +1,105 @@
+use ndarray::{ Array2, Axis };
+
+use super::FeatureError;
+
+/// Computes the first time derivative (delta) of each bin of a CQT magnitude matrix.
+///
+/// The delta of frame `t` is the least-squares slope over the frames `t - width` up to
+/// `t + width`, as for the classic MFCC deltas:
+/// `d_t = Σ_n n * (c_{t+n} - c_{t-n}) / (2 * Σ_n n²)` for `n` in `1..=width`.
+/// Near the edges the window shrinks to the neighbors available on both sides, and the
+/// first and last frames use the one-sided difference with their only neighbor.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `width` - The number of frames on each side of the delta window.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The deltas, of the same shape as the input.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if `width` is zero.
+pub fn deltas(matrix: &Array2<f32>, width: usize) -> Result<Array2<f32>, FeatureError> {
+  if width == 0 {
+    return Err(FeatureError::InvalidWindowLength);
+  }
+
+  let num_frames = matrix.nrows();
+  let mut deltas = Array2::<f32>::zeros(matrix.dim());
+
+  if num_frames < 2 {
+    return Ok(deltas);
+  }
+
+  for (frame, mut delta_row) in deltas.axis_iter_mut(Axis(0)).enumerate() {
+    // Widest symmetric window fitting in the matrix
+    let frame_width = width.min(frame).min(num_frames - 1 - frame);
+
+    if frame_width == 0 {
+      // One-sided difference at the first and last frames
+      let (previous, next) = if frame == 0 { (0, 1) } else { (frame - 1, frame) };
+      delta_row.assign(&(&matrix.row(next) - &matrix.row(previous)));
+      continue;
+    }
+
+    let denominator = 2.0 * (1..=frame_width).map(|n| (n * n) as f32).sum::<f32>();
+
+    for n in 1..=frame_width {
+      let difference = &matrix.row(frame + n) - &matrix.row(frame - n);
+      delta_row.scaled_add((n as f32) / denominator, &difference);
+    }
+  }
+
+  Ok(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_relative_eq;
+  use ndarray::array;
+
+  use super::*;
+
+  #[test]
+  fn test_deltas_linear_ramp() {
+    // A ramp with a slope of 0.5 next to a constant column
+    let matrix = Array2::from_shape_fn((20, 2), |(frame, bin)| {
+      if bin == 0 { 0.5 * (frame as f32) } else { 3.0 }
+    });
+
+    for width in [1, 2, 4] {
+      let deltas = deltas(&matrix, width).unwrap();
+      assert_eq!(deltas.dim(), matrix.dim());
+
+      for delta_row in deltas.axis_iter(Axis(0)) {
+        assert_relative_eq!(delta_row[0], 0.5, max_relative = 1e-5);
+        assert_eq!(delta_row[1], 0.0);
+      }
+    }
+  }
+
+  #[test]
+  fn test_deltas_edges() {
+    let matrix = array![[0.0], [1.0], [4.0], [9.0], [16.0]];
+
+    let deltas = deltas(&matrix, 2).unwrap();
+    assert_eq!(deltas.column(0).to_vec(), vec![1.0, 2.0, 4.0, 6.0, 7.0]);
+  }
+
+  #[test]
+  fn test_deltas_single_frame() {
+    let matrix = array![[1.0, 2.0]];
+
+    assert_eq!(deltas(&matrix, 2), Ok(Array2::<f32>::zeros((1, 2))));
+  }
+
+  #[test]
+  fn test_deltas_zero_width() {
+    let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+
+    assert_eq!(deltas(&matrix, 0), Err(FeatureError::InvalidWindowLength));
+  }
+}
\ No newline at end of file
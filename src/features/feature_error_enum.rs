@@ -0,0 +1,34 @@
+use std::{ error::Error, fmt };
+
+#[derive(Debug, PartialEq)]
+pub enum FeatureError {
+  InvalidBinsPerOctave,
+  BinCountMismatch,
+  InvalidWindowLength,
+  InvalidHopSize,
+}
+
+impl Error for FeatureError {}
+
+// Implement the Display trait for the custom error type
+impl fmt::Display for FeatureError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      FeatureError::InvalidBinsPerOctave => {
+        write!(f, "Invalid bins per octave: bins per octave should be a multiple of 12.")
+      }
+      FeatureError::BinCountMismatch => {
+        write!(
+          f,
+          "Bin count mismatch: the number of columns should be equal to the number of bins."
+        )
+      }
+      FeatureError::InvalidWindowLength => {
+        write!(f, "Invalid window length: window length should be greater than 0.")
+      }
+      FeatureError::InvalidHopSize => {
+        write!(f, "Invalid hop size: hop size should be greater than 0.")
+      }
+    }
+  }
+}
\ No newline at end of file
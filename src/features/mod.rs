@@ -0,0 +1,46 @@
+mod feature_error_enum;
+mod compress_mode_enum;
+mod pool_enum;
+mod cqt_stats_struct;
+mod a_weight;
+mod best_matching_frame;
+mod best_offset;
+mod chroma;
+mod clamp_magnitudes;
+mod compress;
+mod deltas;
+mod normalize_energy;
+mod normalize_per_frame_max;
+mod octave_band_energy;
+mod peaks;
+mod pitch_class_mask;
+mod self_similarity;
+mod semitones;
+mod shift_bins;
+mod spectral_centroid;
+mod tempogram;
+mod time_pool;
+
+pub use feature_error_enum::FeatureError;
+pub use compress_mode_enum::CompressMode;
+pub use pool_enum::Pool;
+pub use cqt_stats_struct::CqtStats;
+pub use a_weight::a_weight;
+pub use best_matching_frame::best_matching_frame;
+pub use best_offset::best_offset;
+pub use chroma::chroma;
+pub(crate) use chroma::{ fold_to_chroma, NUM_PITCH_CLASSES };
+pub use clamp_magnitudes::clamp_magnitudes;
+pub use compress::compress;
+pub use deltas::deltas;
+pub use normalize_energy::normalize_energy;
+pub use normalize_per_frame_max::normalize_per_frame_max;
+pub use octave_band_energy::octave_band_energy;
+pub use peaks::{ peak_bins, refine_peak };
+pub use pitch_class_mask::mask_to_pitch_classes;
+pub use self_similarity::self_similarity;
+pub use semitones::collapse_to_semitones;
+pub use shift_bins::shift_bins;
+pub use spectral_centroid::spectral_centroid;
+pub use tempogram::tempogram;
+pub use time_pool::time_pool;
\ No newline at end of file
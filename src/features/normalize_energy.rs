@@ -0,0 +1,58 @@
+use ndarray::{ Array1, Array2, Axis };
+
+/// Normalizes each frame of a CQT magnitude matrix to unit energy.
+///
+/// Each frame is divided by its L2 norm, so the sum of its squared magnitudes is 1.
+/// The norms are returned along the normalized matrix to restore the dynamics by
+/// multiplying each frame back by its norm. Silent frames are left untouched.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+///
+/// # Returns
+///
+/// * `(Array2<f32>, Array1<f32>)` - The normalized matrix of shape `(num_frames, num_bins)`
+///   and the L2 norm of each frame.
+pub fn normalize_energy(matrix: &Array2<f32>) -> (Array2<f32>, Array1<f32>) {
+  let energies = matrix.map_axis(Axis(1), |frame| frame.dot(&frame).sqrt());
+
+  let mut normalized = matrix.clone();
+  for (mut frame, &energy) in normalized.axis_iter_mut(Axis(0)).zip(energies.iter()) {
+    if energy > 0.0 {
+      frame /= energy;
+    }
+  }
+
+  (normalized, energies)
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_relative_eq;
+  use ndarray::{ array, Zip };
+
+  use super::*;
+
+  #[test]
+  fn test_normalize_energy() {
+    let matrix = array![[3.0, 4.0, 0.0], [0.0, 0.0, 0.0], [0.1, 0.2, 0.5]];
+
+    let (normalized, energies) = normalize_energy(&matrix);
+    assert_eq!(energies[0], 5.0);
+    assert_eq!(energies[1], 0.0);
+    assert_eq!(normalized.row(1), matrix.row(1));
+
+    // The non-silent frames have unit energy
+    for frame_idx in [0, 2] {
+      let frame = normalized.row(frame_idx);
+      assert_relative_eq!(frame.dot(&frame), 1.0, max_relative = 1e-6);
+    }
+
+    // Multiplying back by the energies recovers the matrix
+    let restored = &normalized * &energies.insert_axis(Axis(1));
+    Zip::from(&restored)
+      .and(&matrix)
+      .for_each(|&value, &expected| assert_relative_eq!(value, expected, max_relative = 1e-6));
+  }
+}
\ No newline at end of file
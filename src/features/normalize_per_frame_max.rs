@@ -0,0 +1,44 @@
+use ndarray::{ Array2, Axis };
+
+/// Normalizes each frame of a CQT magnitude matrix to a unit maximum.
+///
+/// Each frame is divided by its largest magnitude, an auto-gain keeping quiet passages
+/// visible when displaying a spectrogram. Unlike `normalize_energy`, the gains aren't
+/// returned as the dynamics aren't meant to be restored. Silent frames are left untouched.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+///
+/// # Returns
+///
+/// * `Array2<f32>` - The normalized matrix, of the same shape as the input.
+pub fn normalize_per_frame_max(matrix: &Array2<f32>) -> Array2<f32> {
+  let mut normalized = matrix.clone();
+
+  for mut frame in normalized.axis_iter_mut(Axis(0)) {
+    let max = frame.fold(0.0f32, |max, &value| max.max(value));
+
+    if max > 0.0 {
+      frame /= max;
+    }
+  }
+
+  normalized
+}
+
+#[cfg(test)]
+mod tests {
+  use ndarray::array;
+
+  use super::*;
+
+  #[test]
+  fn test_normalize_per_frame_max() {
+    let matrix = array![[0.5, 2.0, 1.0], [0.0, 0.0, 0.0], [0.001, 0.004, 0.002]];
+
+    let normalized = normalize_per_frame_max(&matrix);
+
+    assert_eq!(normalized, array![[0.25, 1.0, 0.5], [0.0, 0.0, 0.0], [0.25, 1.0, 0.5]]);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+use ndarray::{ Array2, Axis, s };
+
+use crate::CQTParams;
+
+use super::FeatureError;
+
+/// Sums the energy of a CQT magnitude matrix per octave.
+///
+/// Each octave groups `bins_per_octave` consecutive bins, starting with the bin at
+/// `min_freq`, and its energy is the sum of the squared magnitudes of its bins. With a
+/// number of bins that isn't a multiple of `bins_per_octave`, the last octave is partial
+/// and only sums the remaining bins.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `cqt_params` - The CQTParams used to compute the matrix.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The matrix of shape `(num_frames, num_octaves)`.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if the number of columns of the matrix isn't the number of bins.
+pub fn octave_band_energy(
+  matrix: &Array2<f32>,
+  cqt_params: &CQTParams
+) -> Result<Array2<f32>, FeatureError> {
+  let num_bins = cqt_params.num_bins();
+
+  if matrix.ncols() != num_bins {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let bins_per_octave = cqt_params.bins_per_octave;
+  let num_octaves = num_bins.div_ceil(bins_per_octave);
+
+  let mut energies = Array2::<f32>::zeros((matrix.nrows(), num_octaves));
+
+  for (frame, mut energy_row) in matrix.axis_iter(Axis(0)).zip(energies.axis_iter_mut(Axis(0))) {
+    for (octave, energy_elem) in energy_row.iter_mut().enumerate() {
+      let start = octave * bins_per_octave;
+      let end = (start + bins_per_octave).min(num_bins);
+
+      *energy_elem = frame
+        .slice(s![start..end])
+        .iter()
+        .map(|&value| value * value)
+        .sum();
+    }
+  }
+
+  Ok(energies)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::PI;
+
+  use ndarray::array;
+
+  use crate::Cqt;
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_octave_band_energy() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // A tone at the center of a bin of the fifth octave
+    let freq = cqt.cqt_params().center_freq(4 * BINS_PER_OCTAVE + 5);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let matrix = cqt.process(&signal, 2048).unwrap();
+    let energies = octave_band_energy(&matrix, cqt.cqt_params()).unwrap();
+    assert_eq!(energies.dim(), (matrix.nrows(), 9));
+
+    let frame = energies.row(energies.nrows() / 2);
+    let loudest_octave = frame
+      .iter()
+      .enumerate()
+      .fold(0, |loudest, (idx, &value)| if value > frame[loudest] { idx } else { loudest });
+    assert_eq!(loudest_octave, 4);
+    assert!(frame[4] > 0.9 * frame.sum());
+  }
+
+  #[test]
+  fn test_octave_band_energy_partial_octave() {
+    let cqt_params = CQTParams::with_n_bins(MIN_FREQ, 2, 5, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let matrix = array![[1.0, 2.0, 3.0, 4.0, 5.0]];
+
+    let energies = octave_band_energy(&matrix, &cqt_params).unwrap();
+    assert_eq!(energies, array![[5.0, 25.0, 25.0]]);
+  }
+
+  #[test]
+  fn test_octave_band_energy_bin_count_mismatch() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let matrix = Array2::<f32>::zeros((2, cqt_params.num_bins() + 1));
+
+    assert_eq!(octave_band_energy(&matrix, &cqt_params), Err(FeatureError::BinCountMismatch));
+  }
+}
\ No newline at end of file
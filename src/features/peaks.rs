@@ -0,0 +1,182 @@
+use ndarray::{ Array2, ArrayView1, Axis };
+
+use crate::CQTParams;
+
+/// Picks the spectral peaks of each frame of a CQT magnitude matrix.
+///
+/// A bin is a peak when its magnitude exceeds the threshold and is strictly greater
+/// than both of its neighbors. The first and last bins are only compared against
+/// their existing neighbor.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `threshold` - The magnitude a bin must exceed to be a peak.
+///
+/// # Returns
+///
+/// * `Vec<Vec<usize>>` - The sorted indices of the peak bins of each frame.
+pub fn peak_bins(matrix: &Array2<f32>, threshold: f32) -> Vec<Vec<usize>> {
+  let num_bins = matrix.ncols();
+
+  matrix
+    .axis_iter(Axis(0))
+    .map(|frame| {
+      (0..num_bins)
+        .filter(|&bin| {
+          let value = frame[bin];
+          let above_previous = bin == 0 || value > frame[bin - 1];
+          let above_next = bin + 1 == num_bins || value > frame[bin + 1];
+
+          value > threshold && above_previous && above_next
+        })
+        .collect()
+    })
+    .collect()
+}
+
+/// Refines a spectral peak of a CQT frame to a sub-bin frequency and magnitude.
+///
+/// A parabola is fitted to the log-magnitudes of `bin - 1`, `bin` and `bin + 1`, and its
+/// vertex gives the fractional bin offset and the interpolated magnitude. The first and
+/// last bins, and bins which aren't a strict local maximum of positive magnitudes, are
+/// returned unrefined at their center frequency.
+///
+/// # Arguments
+///
+/// * `column` - The CQT magnitudes of a single frame.
+/// * `bin` - The index of the peak bin to refine.
+/// * `cqt_params` - The CQTParams used to compute the frame.
+///
+/// # Returns
+///
+/// * `(f32, f32)` - The interpolated frequency in Hz and magnitude of the peak.
+///
+/// # Panics
+///
+/// Panics if `bin` is out of bounds of `column`.
+pub fn refine_peak(column: ArrayView1<f32>, bin: usize, cqt_params: &CQTParams) -> (f32, f32) {
+  let center_freq = cqt_params.center_freq(bin);
+  let magnitude = column[bin];
+
+  if bin == 0 || bin + 1 >= column.len() {
+    return (center_freq, magnitude);
+  }
+
+  let (previous, next) = (column[bin - 1], column[bin + 1]);
+  if previous <= 0.0 || next <= 0.0 || magnitude <= previous || magnitude <= next {
+    return (center_freq, magnitude);
+  }
+
+  let (alpha, beta, gamma) = (previous.ln(), magnitude.ln(), next.ln());
+  let offset = (0.5 * (alpha - gamma)) / (alpha - 2.0 * beta + gamma);
+  let log_magnitude = beta - 0.25 * (alpha - gamma) * offset;
+
+  (
+    center_freq * (2.0f32).powf(offset / (cqt_params.bins_per_octave as f32)),
+    log_magnitude.exp(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::PI;
+
+  use ndarray::array;
+
+  use crate::{ CQTParams, Cqt };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_peak_bins_edges() {
+    let matrix = array![[3.0, 1.0, 2.0, 1.0, 4.0], [1.0, 1.0, 0.5, 2.0, 2.0]];
+
+    let peaks = peak_bins(&matrix, 0.5);
+    assert_eq!(peaks, vec![vec![0, 2, 4], vec![]]);
+
+    let peaks = peak_bins(&matrix, 2.5);
+    assert_eq!(peaks, vec![vec![0, 4], vec![]]);
+  }
+
+  #[test]
+  fn test_refine_peak() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // A tone 30% of the way between the centers of two bins
+    let bin = 60;
+    let center_freq = cqt.cqt_params().center_freq(bin);
+    let freq = center_freq * (2.0f32).powf(0.3 / (BINS_PER_OCTAVE as f32));
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let matrix = cqt.process(&signal, 2048).unwrap();
+    let frame = matrix.row(matrix.nrows() / 2);
+    let peak = frame
+      .iter()
+      .enumerate()
+      .fold(0, |peak, (idx, &value)| if value > frame[peak] { idx } else { peak });
+    assert_eq!(peak, bin);
+
+    let (refined_freq, refined_magnitude) = refine_peak(frame, peak, cqt.cqt_params());
+    assert!((refined_freq - freq).abs() < (center_freq - freq).abs());
+    assert!(refined_magnitude >= frame[peak]);
+
+    // The edge bins are returned unrefined
+    let last_bin = frame.len() - 1;
+    assert_eq!(refine_peak(frame, 0, cqt.cqt_params()), (MIN_FREQ, frame[0]));
+    assert_eq!(
+      refine_peak(frame, last_bin, cqt.cqt_params()),
+      (cqt.cqt_params().center_freq(last_bin), frame[last_bin])
+    );
+  }
+
+  #[test]
+  fn test_peak_bins_two_tones() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // Two tones at the center of two bins
+    let low_bin = 48;
+    let high_bin = 72;
+    let low_freq = cqt.cqt_params().center_freq(low_bin);
+    let high_freq = cqt.cqt_params().center_freq(high_bin);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| {
+        let t = (n as f32) / (SAMPLE_RATE as f32);
+        (2.0 * PI * low_freq * t).sin() + (2.0 * PI * high_freq * t).sin()
+      })
+      .collect();
+
+    let matrix = cqt.process(&signal, 2048).unwrap();
+    let threshold = 0.1 * matrix.fold(0.0, |max: f32, &value| max.max(value));
+    let peaks = peak_bins(&matrix, threshold);
+
+    assert_eq!(peaks.len(), matrix.nrows());
+
+    // The frames fully inside the signal hold both tones
+    for frame_peaks in &peaks[2..peaks.len() - 2] {
+      assert_eq!(frame_peaks, &vec![low_bin, high_bin]);
+    }
+  }
+}
\ No newline at end of file
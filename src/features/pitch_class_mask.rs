@@ -0,0 +1,107 @@
+use ndarray::{ Array2, Axis };
+
+use crate::CQTParams;
+
+use super::{ chroma::pitch_class, FeatureError };
+
+/// Zeros the bins of a CQT magnitude matrix whose pitch class isn't in `classes`.
+///
+/// The pitch classes are aligned like in `chroma`: each bin belongs to the pitch class
+/// of its nearest semitone, the pitch class of `min_freq` being `0`.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `cqt_params` - The CQTParams used to compute the matrix.
+/// * `classes` - The pitch classes to keep, from `0` to `11`.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The masked matrix of shape `(num_frames, num_bins)`.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if `bins_per_octave` isn't a multiple of 12 or if the number
+/// of columns of the matrix isn't the number of bins.
+pub fn mask_to_pitch_classes(
+  matrix: &Array2<f32>,
+  cqt_params: &CQTParams,
+  classes: &[u8]
+) -> Result<Array2<f32>, FeatureError> {
+  if cqt_params.bins_per_octave % 12 != 0 {
+    return Err(FeatureError::InvalidBinsPerOctave);
+  }
+
+  if matrix.ncols() != cqt_params.num_bins() {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let mut masked = matrix.clone();
+
+  for (bin, mut column) in masked.axis_iter_mut(Axis(1)).enumerate() {
+    let class = pitch_class(bin, cqt_params.bins_per_octave);
+
+    if !classes.iter().any(|&kept_class| usize::from(kept_class) == class) {
+      column.fill(0.0);
+    }
+  }
+
+  Ok(masked)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::PI;
+
+  use crate::Cqt;
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 32.703; // C1
+  const MAX_FREQ: f32 = 4186.0; // C8
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+  const C_MAJOR: [u8; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+  #[test]
+  fn test_mask_to_pitch_classes() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, 12, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // C4, C#4, E4 and G4
+    let bins = [36, 37, 40, 43];
+    let freqs: Vec<f32> = bins
+      .iter()
+      .map(|&bin| cqt.cqt_params().center_freq(bin))
+      .collect();
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| {
+        let t = (n as f32) / (SAMPLE_RATE as f32);
+        freqs
+          .iter()
+          .map(|freq| (2.0 * PI * freq * t).sin())
+          .sum()
+      })
+      .collect();
+
+    let matrix = cqt.process(&signal, 2048).unwrap();
+    let masked = mask_to_pitch_classes(&matrix, cqt.cqt_params(), &C_MAJOR).unwrap();
+
+    // C, E and G keep their energy while C# is zeroed
+    for bin in [36, 40, 43] {
+      assert_eq!(masked.column(bin), matrix.column(bin));
+      assert!(masked.column(bin).sum() > 0.0);
+    }
+    assert!(matrix.column(37).sum() > 0.0);
+    assert!(masked.column(37).iter().all(|&value| value == 0.0));
+  }
+
+  #[test]
+  fn test_mask_to_pitch_classes_invalid_bins_per_octave() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, 10, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let matrix = Array2::<f32>::zeros((2, cqt_params.num_bins()));
+
+    let result = mask_to_pitch_classes(&matrix, &cqt_params, &C_MAJOR);
+    assert_eq!(result, Err(FeatureError::InvalidBinsPerOctave));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+/// The pooling applied to a group of CQT values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pool {
+  Max,
+  Mean,
+}
+
+impl Pool {
+  /// Pool a group of values into a single value.
+  ///
+  /// # Arguments
+  ///
+  /// * `values` - The values to pool, must not be empty.
+  ///
+  /// # Returns
+  ///
+  /// * `f32` - The maximum or the mean of the values.
+  pub fn apply<'a, I>(&self, values: I) -> f32 where I: ExactSizeIterator<Item = &'a f32> {
+    match self {
+      Pool::Max => values.fold(f32::MIN, |max, &value| max.max(value)),
+      Pool::Mean => {
+        let len = values.len() as f32;
+        values.sum::<f32>() / len
+      }
+    }
+  }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use ndarray::{ Array2, Axis };
+
+/// Computes the self-similarity matrix of a CQT magnitude matrix.
+///
+/// Element `(i, j)` is the cosine similarity between frames `i` and `j`,
+/// `frame_i · frame_j / (|frame_i| * |frame_j|)`. The similarity of a silent frame with
+/// any frame, itself included, is 0.
+///
+/// The cost grows with the square of the number of frames, `O(num_frames² * num_bins)` in
+/// time and `O(num_frames²)` in memory. The frames are normalized once and multiplied as a
+/// single matrix product.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+///
+/// # Returns
+///
+/// * `Array2<f32>` - The symmetric similarity matrix of shape `(num_frames, num_frames)`.
+pub fn self_similarity(matrix: &Array2<f32>) -> Array2<f32> {
+  let mut normalized = matrix.to_owned();
+
+  for mut frame in normalized.axis_iter_mut(Axis(0)) {
+    let norm = frame.dot(&frame).sqrt();
+
+    if norm > 0.0 {
+      frame.mapv_inplace(|value| value / norm);
+    }
+  }
+
+  normalized.dot(&normalized.t())
+}
+
+#[cfg(test)]
+mod tests {
+  use approx::assert_relative_eq;
+  use ndarray::Zip;
+
+  use super::*;
+
+  #[test]
+  fn test_self_similarity() {
+    let matrix = Array2::from_shape_fn((20, 12), |(frame, bin)| {
+      ((frame as f32) * 0.7 + (bin as f32) * 1.3).sin().abs() + 0.01
+    });
+
+    let similarity = self_similarity(&matrix);
+
+    assert_eq!(similarity.dim(), (20, 20));
+
+    for frame_idx in 0..20 {
+      assert_relative_eq!(similarity[[frame_idx, frame_idx]], 1.0, max_relative = 1e-5);
+    }
+
+    Zip::from(&similarity)
+      .and(similarity.t())
+      .for_each(|&value, &transposed_value| {
+        assert_relative_eq!(value, transposed_value, max_relative = 1e-6);
+      });
+    assert!(similarity.iter().all(|&value| value <= 1.0 + 1e-5));
+  }
+
+  #[test]
+  fn test_self_similarity_silent_frame() {
+    let mut matrix = Array2::<f32>::ones((3, 4));
+    matrix.row_mut(1).fill(0.0);
+
+    let similarity = self_similarity(&matrix);
+
+    assert_eq!(similarity.row(1).sum(), 0.0);
+    assert_eq!(similarity.column(1).sum(), 0.0);
+    assert_relative_eq!(similarity[[0, 2]], 1.0, max_relative = 1e-6);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+use ndarray::{ Array2, Axis, s };
+
+use crate::CQTParams;
+
+use super::{ FeatureError, Pool };
+
+/// Collapses a CQT magnitude matrix to one bin per semitone.
+///
+/// With `bins_per_octave = 12 * r`, each semitone groups the `r` bins nearest to its pitch,
+/// centered on the bin at the pitch of the semitone, so each bin goes to its nearest semitone
+/// like in `chroma`. The pitch of the first semitone is `min_freq`, and the groups are clamped
+/// to the bins of the matrix.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `cqt_params` - The CQTParams used to compute the matrix.
+/// * `pool` - The pooling applied within each semitone.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The matrix of shape `(num_frames, num_semitones)`.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if `bins_per_octave` isn't a multiple of 12 or if the number
+/// of columns of the matrix isn't the number of bins.
+pub fn collapse_to_semitones(
+  matrix: &Array2<f32>,
+  cqt_params: &CQTParams,
+  pool: Pool
+) -> Result<Array2<f32>, FeatureError> {
+  if cqt_params.bins_per_octave % 12 != 0 {
+    return Err(FeatureError::InvalidBinsPerOctave);
+  }
+
+  let num_bins = cqt_params.num_bins();
+
+  if matrix.ncols() != num_bins {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  // Number of bins per semitone
+  let bins_per_semitone = cqt_params.bins_per_octave / 12;
+  let num_semitones = num_bins.div_ceil(bins_per_semitone);
+
+  let mut semitones = Array2::<f32>::zeros((matrix.nrows(), num_semitones));
+
+  for (frame, mut semitone_row) in matrix.axis_iter(Axis(0)).zip(semitones.axis_iter_mut(Axis(0))) {
+    for (semitone, semitone_elem) in semitone_row.iter_mut().enumerate() {
+      // The bins rounding to the semitone, ties going to the higher semitone
+      let center = semitone * bins_per_semitone;
+      let start = center.saturating_sub(bins_per_semitone / 2);
+      let end = (center + bins_per_semitone.div_ceil(2)).min(num_bins);
+
+      *semitone_elem = pool.apply(frame.slice(s![start..end]).iter());
+    }
+  }
+
+  Ok(semitones)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{ create_dummy_audio_signal, Cqt };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 27.5; // A0
+  const MAX_FREQ: f32 = 4186.0; // C8
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_collapse_to_semitones() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, 36, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, 440.0, 0.5);
+    let matrix = cqt.process(&signal, 2048).unwrap();
+
+    for pool in [Pool::Max, Pool::Mean] {
+      let semitones = collapse_to_semitones(&matrix, cqt.cqt_params(), pool).unwrap();
+      assert_eq!(semitones.dim(), (matrix.nrows(), matrix.ncols() / 3));
+
+      // A4 is 48 semitones above A0
+      let frame = semitones.row(semitones.nrows() / 2);
+      let peak = frame
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > frame[peak] { idx } else { peak });
+      assert_eq!(peak, 48);
+    }
+  }
+
+  #[test]
+  fn test_collapse_to_semitones_flat_tone() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, 36, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let cqt = Cqt::new(cqt_params);
+    // A third of a semitone below A4, the bin just below the A4 bin
+    let freq = 440.0 * (2f32).powf(-1.0 / 36.0);
+    let signal = create_dummy_audio_signal(SAMPLE_RATE, freq, 0.5);
+    let matrix = cqt.process(&signal, 2048).unwrap();
+
+    for pool in [Pool::Max, Pool::Mean] {
+      let semitones = collapse_to_semitones(&matrix, cqt.cqt_params(), pool).unwrap();
+
+      // The tone goes to A4 like in `chroma`, not to G#4
+      let frame = semitones.row(semitones.nrows() / 2);
+      let peak = frame
+        .iter()
+        .enumerate()
+        .fold(0, |peak, (idx, &value)| if value > frame[peak] { idx } else { peak });
+      assert_eq!(peak, 48);
+    }
+  }
+
+  #[test]
+  fn test_collapse_to_semitones_invalid_bins_per_octave() {
+    let cqt_params = CQTParams::new(MIN_FREQ, MAX_FREQ, 10, SAMPLE_RATE, WINDOW_LENGTH).unwrap();
+    let matrix = Array2::<f32>::zeros((2, cqt_params.num_bins()));
+
+    let result = collapse_to_semitones(&matrix, &cqt_params, Pool::Max);
+    assert_eq!(result, Err(FeatureError::InvalidBinsPerOctave));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use ndarray::{ Array2, s };
+
+/// Shifts the bins of a CQT magnitude matrix, approximating a pitch shift.
+///
+/// Since the CQT bins are geometrically spaced, shifting by `n` bins approximates a pitch
+/// shift by `n / bins_per_octave` octaves without recomputing the transform. The bins
+/// shifted out of the matrix are dropped and the vacated bins are filled with zeros.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `n` - The number of bins to shift by, upward when positive and downward when negative.
+///
+/// # Returns
+///
+/// * `Array2<f32>` - The shifted matrix of shape `(num_frames, num_bins)`.
+pub fn shift_bins(matrix: &Array2<f32>, n: isize) -> Array2<f32> {
+  let num_bins = matrix.ncols();
+  let shift = n.unsigned_abs().min(num_bins);
+
+  let mut shifted = Array2::<f32>::zeros(matrix.dim());
+
+  if n >= 0 {
+    shifted.slice_mut(s![.., shift..]).assign(&matrix.slice(s![.., ..num_bins - shift]));
+  } else {
+    shifted.slice_mut(s![.., ..num_bins - shift]).assign(&matrix.slice(s![.., shift..]));
+  }
+
+  shifted
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::PI;
+
+  use ndarray::{ array, ArrayView1 };
+
+  use crate::{ CQTParams, Cqt };
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  fn peak_bin(frame: ArrayView1<f32>) -> usize {
+    frame
+      .iter()
+      .enumerate()
+      .fold(0, |peak, (bin, &value)| if value > frame[peak] { bin } else { peak })
+  }
+
+  #[test]
+  fn test_shift_bins() {
+    let matrix = array![[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]];
+
+    assert_eq!(shift_bins(&matrix, 0), matrix);
+    assert_eq!(shift_bins(&matrix, 1), array![[0.0, 1.0, 2.0, 3.0], [0.0, 5.0, 6.0, 7.0]]);
+    assert_eq!(shift_bins(&matrix, -2), array![[3.0, 4.0, 0.0, 0.0], [7.0, 8.0, 0.0, 0.0]]);
+    assert_eq!(shift_bins(&matrix, 5), Array2::zeros((2, 4)));
+    assert_eq!(shift_bins(&matrix, -5), Array2::zeros((2, 4)));
+  }
+
+  #[test]
+  fn test_shift_bins_octave() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // A4
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * 440.0 * ((n as f32) / (SAMPLE_RATE as f32))).sin())
+      .collect();
+
+    let matrix = cqt.process(&signal, 2048).unwrap();
+    let shifted = shift_bins(&matrix, BINS_PER_OCTAVE as isize);
+
+    let frame = matrix.nrows() / 2;
+    let a4_bin = peak_bin(matrix.row(frame));
+    assert_eq!(peak_bin(shifted.row(frame)), a4_bin + BINS_PER_OCTAVE);
+    assert_eq!(shifted[[frame, a4_bin + BINS_PER_OCTAVE]], matrix[[frame, a4_bin]]);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,100 @@
+use ndarray::{ Array1, Array2, Axis };
+
+use crate::CQTParams;
+
+use super::FeatureError;
+
+/// Computes the spectral centroid of each frame of a CQT magnitude matrix.
+///
+/// The centroid is the magnitude-weighted mean of the center frequencies of the bins,
+/// `sum(magnitude * center_freq) / sum(magnitude)`. The centroid of a silent frame is 0.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `cqt_params` - The CQTParams used to compute the matrix.
+///
+/// # Returns
+///
+/// * `Result<Array1<f32>, FeatureError>` - The centroid of each frame in Hz.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if the number of columns of the matrix isn't the number of bins.
+pub fn spectral_centroid(
+  matrix: &Array2<f32>,
+  cqt_params: &CQTParams
+) -> Result<Array1<f32>, FeatureError> {
+  if matrix.ncols() != cqt_params.num_bins() {
+    return Err(FeatureError::BinCountMismatch);
+  }
+
+  let center_freqs = cqt_params.center_freqs();
+
+  Ok(
+    matrix.map_axis(Axis(1), |frame| {
+      let total_magnitude = frame.sum();
+
+      if total_magnitude > 0.0 { frame.dot(&center_freqs) / total_magnitude } else { 0.0 }
+    })
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::PI;
+
+  use approx::assert_relative_eq;
+
+  use crate::Cqt;
+
+  use super::*;
+
+  const MIN_FREQ: f32 = 20.0;
+  const MAX_FREQ: f32 = 7902.1;
+  const BINS_PER_OCTAVE: usize = 12;
+  const SAMPLE_RATE: usize = 44100;
+  const WINDOW_LENGTH: usize = 4096;
+
+  #[test]
+  fn test_spectral_centroid() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let cqt = Cqt::new(cqt_params);
+
+    // A tone at the center of a bin
+    let freq = cqt.cqt_params().center_freq(72);
+    let signal: Vec<f32> = (0..SAMPLE_RATE / 2)
+      .map(|n| (2.0 * PI * freq * (n as f32) / (SAMPLE_RATE as f32)).sin())
+      .collect();
+
+    let matrix = cqt.process(&signal, 2048).unwrap();
+    let centroids = spectral_centroid(&matrix, cqt.cqt_params()).unwrap();
+    assert_eq!(centroids.len(), matrix.nrows());
+    assert_relative_eq!(centroids[centroids.len() / 2], freq, max_relative = 0.05);
+
+    // Silent frames have a null centroid
+    let silent = Array2::<f32>::zeros((2, cqt.cqt_params().num_bins()));
+    let centroids = spectral_centroid(&silent, cqt.cqt_params()).unwrap();
+    assert_eq!(centroids, Array1::<f32>::zeros(2));
+  }
+
+  #[test]
+  fn test_spectral_centroid_bin_count_mismatch() {
+    let cqt_params = CQTParams::new(
+      MIN_FREQ,
+      MAX_FREQ,
+      BINS_PER_OCTAVE,
+      SAMPLE_RATE,
+      WINDOW_LENGTH
+    ).unwrap();
+    let matrix = Array2::<f32>::zeros((2, cqt_params.num_bins() + 1));
+
+    assert_eq!(spectral_centroid(&matrix, &cqt_params), Err(FeatureError::BinCountMismatch));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,100 @@
+use ndarray::{ Array1, Array2, Axis, parallel::prelude::* };
+
+use super::FeatureError;
+
+/// Computes the tempogram of an onset envelope.
+///
+/// The tempogram is the windowed local autocorrelation of the onset envelope: row `i`
+/// holds the autocorrelation, for lags `0..win`, of the `win` samples centered on
+/// `i * hop`. Samples falling outside of the envelope at the start and end are zero.
+/// Periodic onsets show up as peaks at the lag of their period.
+///
+/// # Arguments
+///
+/// * `onset_env` - The onset envelope, one value per CQT frame.
+/// * `win` - The length of the autocorrelation window in frames.
+/// * `hop` - The number of envelope frames between two tempogram rows.
+///
+/// # Returns
+///
+/// * `Result<Array2<f32>, FeatureError>` - The tempogram of shape `(num_rows, win)`.
+///
+/// # Errors
+///
+/// Returns a `FeatureError` if `win` or `hop` is zero.
+pub fn tempogram(
+  onset_env: &Array1<f32>,
+  win: usize,
+  hop: usize
+) -> Result<Array2<f32>, FeatureError> {
+  if win == 0 {
+    return Err(FeatureError::InvalidWindowLength);
+  }
+
+  if hop == 0 {
+    return Err(FeatureError::InvalidHopSize);
+  }
+
+  let env_len = onset_env.len();
+  let num_rows = env_len.div_ceil(hop);
+  let half_win = win / 2;
+
+  let mut tempogram = Array2::<f32>::zeros((num_rows, win));
+
+  tempogram
+    .axis_iter_mut(Axis(0))
+    .into_par_iter()
+    .enumerate()
+    .for_each(|(row_idx, mut row)| {
+      // Zero-padded window of the envelope centered on the row
+      let center = row_idx * hop;
+      let window: Vec<f32> = (0..win)
+        .map(|n| {
+          (center + n)
+            .checked_sub(half_win)
+            .and_then(|idx| onset_env.get(idx))
+            .copied()
+            .unwrap_or(0.0)
+        })
+        .collect();
+
+      // Autocorrelation of the window
+      for (lag, row_elem) in row.iter_mut().enumerate() {
+        *row_elem = window
+          .iter()
+          .zip(window[lag..].iter())
+          .map(|(a, b)| a * b)
+          .sum();
+      }
+    });
+
+  Ok(tempogram)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_tempogram_periodic_onsets() {
+    const PERIOD: usize = 8;
+    const WIN: usize = 32;
+
+    let onset_env = Array1::from_shape_fn(256, |n| if n % PERIOD == 0 { 1.0 } else { 0.0 });
+    let tempogram = tempogram(&onset_env, WIN, 4).unwrap();
+    assert_eq!(tempogram.dim(), (64, WIN));
+
+    // Ignoring the zero lag, the autocorrelation peaks at the period
+    let row = tempogram.row(tempogram.nrows() / 2);
+    let peak_lag = (1..WIN).fold(1, |peak, lag| if row[lag] > row[peak] { lag } else { peak });
+    assert_eq!(peak_lag, PERIOD);
+  }
+
+  #[test]
+  fn test_tempogram_invalid_hop() {
+    let onset_env = Array1::<f32>::zeros(16);
+
+    assert_eq!(tempogram(&onset_env, 4, 0), Err(FeatureError::InvalidHopSize));
+    assert_eq!(tempogram(&onset_env, 0, 4), Err(FeatureError::InvalidWindowLength));
+  }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use ndarray::{ Array2, Axis, s };
+
+use super::Pool;
+
+/// Pools the frames of a CQT magnitude matrix into a fixed number of frames.
+///
+/// The frames are grouped into `target_frames` consecutive buckets, bucket `i` holding the
+/// frames `i * num_frames / target_frames` up to `(i + 1) * num_frames / target_frames`.
+/// When the number of frames isn't a multiple of `target_frames`, the remainder frames are
+/// spread across the buckets, so their sizes differ by at most one frame. When there are fewer
+/// frames than `target_frames`, each bucket holds the single frame it falls on. A matrix
+/// without frames, e.g. the CQT of a signal shorter than the hop size, is pooled to zeros.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `target_frames` - The number of frames of the pooled matrix.
+/// * `pool` - The pooling applied within each bucket.
+///
+/// # Returns
+///
+/// * `Array2<f32>` - The matrix of shape `(target_frames, num_bins)`.
+pub fn time_pool(matrix: &Array2<f32>, target_frames: usize, pool: Pool) -> Array2<f32> {
+  let num_frames = matrix.nrows();
+
+  let mut pooled = Array2::<f32>::zeros((target_frames, matrix.ncols()));
+
+  if num_frames == 0 {
+    return pooled;
+  }
+
+  for (bucket, mut pooled_row) in pooled.axis_iter_mut(Axis(0)).enumerate() {
+    let start = (bucket * num_frames) / target_frames;
+    let end = (((bucket + 1) * num_frames) / target_frames).max(start + 1);
+    let frames = matrix.slice(s![start..end, ..]);
+
+    for (pooled_elem, column) in pooled_row.iter_mut().zip(frames.axis_iter(Axis(1))) {
+      *pooled_elem = pool.apply(column.iter());
+    }
+  }
+
+  pooled
+}
+
+#[cfg(test)]
+mod tests {
+  use ndarray::{ array, Array1 };
+
+  use super::*;
+
+  #[test]
+  fn test_time_pool() {
+    // A constant column next to a ramp
+    let matrix = Array2::from_shape_fn((100, 2), |(frame, bin)| {
+      if bin == 0 { 0.5 } else { frame as f32 }
+    });
+
+    for pool in [Pool::Max, Pool::Mean] {
+      let pooled = time_pool(&matrix, 10, pool);
+
+      assert_eq!(pooled.dim(), (10, 2));
+      assert_eq!(pooled.column(0), Array1::from_elem(10, 0.5));
+    }
+
+    let pooled = time_pool(&matrix, 10, Pool::Max);
+    assert_eq!(pooled[[0, 1]], 9.0);
+    assert_eq!(pooled[[9, 1]], 99.0);
+
+    let pooled = time_pool(&matrix, 10, Pool::Mean);
+    assert_eq!(pooled[[0, 1]], 4.5);
+    assert_eq!(pooled[[9, 1]], 94.5);
+  }
+
+  #[test]
+  fn test_time_pool_remainder() {
+    let matrix = array![[1.0], [2.0], [3.0], [4.0], [5.0]];
+
+    // The buckets hold 1, 2 and 2 frames
+    let pooled = time_pool(&matrix, 3, Pool::Mean);
+    assert_eq!(pooled, array![[1.0], [2.5], [4.5]]);
+
+    // Each bucket repeats the frame it falls on
+    let pooled = time_pool(&matrix, 10, Pool::Max);
+    assert_eq!(pooled.column(0).to_vec(), vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0]);
+  }
+
+  #[test]
+  fn test_time_pool_empty() {
+    let matrix = Array2::<f32>::zeros((0, 3));
+
+    for pool in [Pool::Max, Pool::Mean] {
+      assert_eq!(time_pool(&matrix, 4, pool), Array2::<f32>::zeros((4, 3)));
+      assert_eq!(time_pool(&matrix, 0, pool).dim(), (0, 3));
+    }
+  }
+}
\ No newline at end of file
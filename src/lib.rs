@@ -4,16 +4,81 @@ mod complex_hann_window;
 mod cqt_filterbank;
 mod constant_q_transform;
 mod examples;
+mod features;
+#[cfg(feature = "image")]
+mod spectrogram_image;
 
-pub use calculations::{ get_calculated_phase_factors, get_calculated_base_freq_ratio };
-pub use common::{ CQTParams, CQTParamsError };
+pub use calculations::{
+  calculate_kaiser_window,
+  get_calculated_phase_factors,
+  get_calculated_base_freq_ratio,
+};
+pub use common::{
+  Centering,
+  CQTParams,
+  CQTParamsBuilder,
+  CQTParamsError,
+  CQTParamsKey,
+  FilterNorm,
+  PhaseConvention,
+  WindowRounding,
+  WindowType,
+};
 pub use complex_hann_window::{
   create_complex_hann_window,
+  calculate_bin_norm_factors,
   calculate_norm,
+  get_calculated_norm_factor,
   get_calculated_q_factor,
 };
-pub use constant_q_transform::Cqt;
+pub use constant_q_transform::{
+  chunk_overlap_frames,
+  chunk_signal,
+  concat_cqt,
+  frame_rms,
+  validate_frame,
+  Cqt,
+  ExportedFrames,
+  SignalError,
+};
 
-pub use cqt_filterbank::compute_cqt_filterbank;
+pub use cqt_filterbank::{
+  compute_cqt_filterbank,
+  compute_cqt_filterbank_with_freqs,
+  compute_cqt_filterbank_with_planner,
+  compute_octave_filterbanks,
+  compute_octave_filterbanks_with_planner,
+  CQTFilterbankError,
+  OctaveFilterbank,
+};
+
+pub use examples::{ approx_equal, create_dummy_audio_signal };
+
+pub use features::{
+  a_weight,
+  best_matching_frame,
+  best_offset,
+  chroma,
+  clamp_magnitudes,
+  collapse_to_semitones,
+  compress,
+  deltas,
+  mask_to_pitch_classes,
+  normalize_energy,
+  normalize_per_frame_max,
+  octave_band_energy,
+  peak_bins,
+  refine_peak,
+  self_similarity,
+  shift_bins,
+  spectral_centroid,
+  tempogram,
+  time_pool,
+  CompressMode,
+  CqtStats,
+  FeatureError,
+  Pool,
+};
 
-pub use examples::create_dummy_audio_signal;
\ No newline at end of file
+#[cfg(feature = "image")]
+pub use spectrogram_image::{ save_spectrogram_png, Colormap, SpectrogramImageError };
\ No newline at end of file
@@ -0,0 +1,79 @@
+/// The colormap used to render a spectrogram.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Colormap {
+  Viridis,
+  Magma,
+}
+
+// Colors of the matplotlib colormaps sampled at 9 evenly spaced positions
+const VIRIDIS: [[u8; 3]; 9] = [
+  [68, 1, 84],
+  [71, 44, 122],
+  [59, 81, 139],
+  [44, 113, 142],
+  [33, 144, 141],
+  [39, 173, 129],
+  [92, 200, 99],
+  [170, 220, 50],
+  [253, 231, 37],
+];
+const MAGMA: [[u8; 3]; 9] = [
+  [0, 0, 4],
+  [28, 16, 68],
+  [79, 18, 123],
+  [129, 37, 129],
+  [181, 54, 122],
+  [229, 80, 100],
+  [251, 135, 97],
+  [254, 194, 135],
+  [252, 253, 191],
+];
+
+impl Colormap {
+  /// Map a value to its color, interpolating linearly between the sampled colors.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The value to map, clamped to `[0, 1]`.
+  ///
+  /// # Returns
+  ///
+  /// * `[u8; 3]` - The RGB color of the value.
+  pub fn color(&self, value: f32) -> [u8; 3] {
+    let colors = match self {
+      Colormap::Viridis => &VIRIDIS,
+      Colormap::Magma => &MAGMA,
+    };
+
+    let position = value.clamp(0.0, 1.0) * ((colors.len() - 1) as f32);
+    let lower = (position.floor() as usize).min(colors.len() - 2);
+    let fraction = position - (lower as f32);
+
+    let mut color = [0; 3];
+    for (channel, color_elem) in color.iter_mut().enumerate() {
+      let lower_value = colors[lower][channel] as f32;
+      let upper_value = colors[lower + 1][channel] as f32;
+
+      *color_elem = (lower_value + fraction * (upper_value - lower_value)).round() as u8;
+    }
+
+    color
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_colormap_color() {
+    assert_eq!(Colormap::Viridis.color(0.0), VIRIDIS[0]);
+    assert_eq!(Colormap::Viridis.color(1.0), VIRIDIS[8]);
+    assert_eq!(Colormap::Magma.color(0.5), MAGMA[4]);
+    assert_eq!(Colormap::Magma.color(-1.0), MAGMA[0]);
+    assert_eq!(Colormap::Magma.color(2.0), MAGMA[8]);
+
+    // Halfway between the first two colors
+    assert_eq!(Colormap::Viridis.color(0.0625), [70, 23, 103]);
+  }
+}
\ No newline at end of file
@@ -0,0 +1,97 @@
+mod colormap_enum;
+mod spectrogram_image_error_enum;
+
+use std::{ fs::File, io::BufWriter, path::Path };
+
+use ndarray::{ Array2, Axis };
+
+pub use colormap_enum::Colormap;
+pub use spectrogram_image_error_enum::SpectrogramImageError;
+
+/// The lowest level of the spectrogram in dB relative to the matrix maximum.
+const FLOOR_DB: f32 = -80.0;
+
+/// Saves a CQT magnitude matrix as a PNG spectrogram.
+///
+/// The magnitudes are converted to dB relative to the matrix maximum, floored at -80 dB,
+/// and mapped to the colormap. The image has one column per frame and one row per bin,
+/// with the lowest bin at the bottom.
+///
+/// # Arguments
+///
+/// * `matrix` - The CQT magnitude matrix of shape `(num_frames, num_bins)`.
+/// * `path` - The path of the PNG file to write.
+/// * `colormap` - The colormap of the spectrogram.
+///
+/// # Errors
+///
+/// Returns a `SpectrogramImageError` if the matrix is empty or the file can't be written.
+pub fn save_spectrogram_png<P: AsRef<Path>>(
+  matrix: &Array2<f32>,
+  path: P,
+  colormap: Colormap
+) -> Result<(), SpectrogramImageError> {
+  let (num_frames, num_bins) = matrix.dim();
+
+  if num_frames == 0 || num_bins == 0 {
+    return Err(SpectrogramImageError::EmptyMatrix);
+  }
+
+  let max_value = matrix.fold(0.0f32, |max, &value| max.max(value));
+
+  // One RGB pixel per frame and bin, the highest bin on the first row
+  let mut pixels = Vec::with_capacity(num_frames * num_bins * 3);
+  for bin_column in matrix.axis_iter(Axis(1)).rev() {
+    for &value in bin_column.iter() {
+      let level_db = (20.0 * (value / max_value).log10()).max(FLOOR_DB);
+      pixels.extend_from_slice(&colormap.color(1.0 - level_db / FLOOR_DB));
+    }
+  }
+
+  let file = File::create(path).map_err(|_| SpectrogramImageError::WriteError)?;
+  let mut encoder = png::Encoder::new(BufWriter::new(file), num_frames as u32, num_bins as u32);
+  encoder.set_color(png::ColorType::Rgb);
+  encoder.set_depth(png::BitDepth::Eight);
+
+  encoder
+    .write_header()
+    .and_then(|mut writer| writer.write_image_data(&pixels))
+    .map_err(|_| SpectrogramImageError::WriteError)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{ env, fs };
+
+  use super::*;
+
+  #[test]
+  fn test_save_spectrogram_png() {
+    let path = env::temp_dir().join("cqt_rs_test_spectrogram.png");
+    let matrix = Array2::from_shape_fn((5, 3), |(frame, bin)| (frame * bin) as f32);
+
+    save_spectrogram_png(&matrix, &path, Colormap::Viridis).unwrap();
+
+    let decoder = png::Decoder::new(File::open(&path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!((info.width, info.height), (5, 3));
+    assert_eq!(info.color_type, png::ColorType::Rgb);
+    // The maximum, at the last frame of the highest bin, is in the top right corner
+    assert_eq!(pixels[4 * 3..5 * 3], Colormap::Viridis.color(1.0));
+    // The zeros of the lowest bin are at the floor
+    assert_eq!(pixels[2 * 5 * 3..2 * 5 * 3 + 3], Colormap::Viridis.color(0.0));
+  }
+
+  #[test]
+  fn test_save_spectrogram_png_empty_matrix() {
+    let path = env::temp_dir().join("cqt_rs_test_empty_spectrogram.png");
+    let matrix = Array2::<f32>::zeros((0, 3));
+
+    let result = save_spectrogram_png(&matrix, path, Colormap::Magma);
+    assert_eq!(result, Err(SpectrogramImageError::EmptyMatrix));
+  }
+}
\ No newline at end of file
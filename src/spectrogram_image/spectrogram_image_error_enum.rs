@@ -0,0 +1,23 @@
+use std::{ error::Error, fmt };
+
+#[derive(Debug, PartialEq)]
+pub enum SpectrogramImageError {
+  EmptyMatrix,
+  WriteError,
+}
+
+impl Error for SpectrogramImageError {}
+
+// Implement the Display trait for the custom error type
+impl fmt::Display for SpectrogramImageError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SpectrogramImageError::EmptyMatrix => {
+        write!(f, "Empty matrix: the matrix should have at least one frame and one bin.")
+      }
+      SpectrogramImageError::WriteError => {
+        write!(f, "Write error: the PNG file could not be written.")
+      }
+    }
+  }
+}
\ No newline at end of file